@@ -0,0 +1,55 @@
+//! Compares hot loops over concrete values with the same loops over erased
+//! values. Erasure is the identity, so each pair should take the same time,
+//! within noise; a consistent gap means the erased path stopped inlining.
+//!
+//! Run with `cargo bench`; Criterion keeps the last run as a baseline, so a
+//! regression shows up as a change from it.
+
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate whiteout;
+
+use criterion::Criterion;
+use std::hint::black_box;
+use std::ops::Add;
+
+const ITERATIONS: u32 = 10_000;
+
+eraser!(erase_add, Add<i32, Output = i32>);
+
+fn arithmetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arithmetic");
+    group.bench_function("concrete", |b| b.iter(|| {
+        let mut total = 0i32;
+        for i in 0..ITERATIONS {
+            total = black_box(total.wrapping_add(i as i32)) + 1;
+        }
+        total
+    }));
+    group.bench_function("erased", |b| b.iter(|| {
+        let mut total = 0i32;
+        for i in 0..ITERATIONS {
+            total = erase_add(black_box(total.wrapping_add(i as i32))) + 1;
+        }
+        total
+    }));
+    group.finish();
+}
+
+fn iterator(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iterator");
+    group.bench_function("concrete", |b| b.iter(|| {
+        (0..black_box(ITERATIONS as i32))
+            .filter(|x| x % 3 == 0)
+            .fold(0i32, |a, b| a.wrapping_add(b))
+    }));
+    group.bench_function("erased", |b| b.iter(|| {
+        erase_iter!((0..black_box(ITERATIONS as i32)).filter(|x| x % 3 == 0), i32)
+            .fold(0i32, |a, b| a.wrapping_add(b))
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, arithmetic, iterator);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+//! With the `codegen` feature on, writes erasers made by the `codegen` module
+//! to `OUT_DIR`, where its tests include them, so that they check that the
+//! generated source compiles.
+
+extern crate alloc;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/codegen.rs"]
+mod codegen;
+
+use codegen::{eraser_source, Visibility};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/codegen.rs");
+    if env::var_os("CARGO_FEATURE_CODEGEN").is_none() {
+        return;
+    }
+    let source = [
+        eraser_source("erase_debug", "std::fmt::Debug", Visibility::Public),
+        eraser_source("erase_add", "std::ops::Add<i64, Output=i64>", Visibility::Crate),
+        eraser_source("erase_clone", "Clone", Visibility::Private),
+    ].concat();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("codegen_erasers.rs"), source).unwrap();
+}
@@ -0,0 +1,103 @@
+//! A runtime tracer of erasure sites, for finding out how many distinct
+//! anonymous types a program's erasers make; enabled by the `bloat-report`
+//! feature.
+//!
+//! Every function made by `eraser!`, and so every `erase!` and the macros
+//! built on it, registers its site and the type it was called with when it
+//! runs, so each instantiation of a generic eraser is counted once. Only the
+//! erasers which run are traced; for a count of every site in a crate's
+//! source, made while the crate builds, see `codegen::write_bloat_report`.
+//!
+//! Each site remembers the last type it registered, so an eraser called
+//! again with the same type skips the registry's lock. A site called with
+//! types in turn takes the lock on every change, so the feature is for
+//! diagnostics rather than for release builds.
+//!
+//! # Examples
+//!
+//! ```
+//! #[macro_use]
+//! extern crate whiteout;
+//!
+//! fn main() {
+//!     let a = erase!(1, std::fmt::Debug);
+//!     let b = erase!("two", std::fmt::Display);
+//!     let _ = format!("{:?} {}", a, b);
+//!
+//!     let sites = whiteout::bloat::sites();
+//!     assert!(sites.iter().any(|s| s.trait_name == "std::fmt::Debug"));
+//!     assert!(sites.iter().any(|s| s.trait_name == "std::fmt::Display"));
+//!     print!("{}", whiteout::bloat::report());
+//! }
+//! ```
+
+use std::fmt::Write;
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::vec::Vec;
+use std::string::String;
+
+/// A place in the source where an eraser was defined, and a type it was
+/// called with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Site {
+    /// The trait erased to, as it was written.
+    pub trait_name: &'static str,
+    /// The module containing the eraser.
+    pub module: &'static str,
+    /// The file containing the eraser.
+    pub file: &'static str,
+    /// The line of the outermost macro call that made the eraser.
+    pub line: u32,
+    /// The type of the value erased.
+    pub type_name: &'static str,
+}
+
+static SITES: Mutex<Vec<Site>> = Mutex::new(Vec::new());
+
+/// The name of the last type a site registered. Used by the macros, which
+/// keep one in a `static` at each site.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Seen(AtomicPtr<u8>);
+
+impl Seen {
+    pub const fn new() -> Seen {
+        Seen(AtomicPtr::new(ptr::null_mut()))
+    }
+}
+
+/// Records a site. Used by the macros; each is only recorded once.
+#[doc(hidden)]
+pub fn register(seen: &Seen, site: Site) {
+    // Type names are static strings, so the same type gives the same pointer
+    let name = site.type_name.as_ptr() as *mut u8;
+    if seen.0.load(Ordering::Relaxed) == name {
+        return;
+    }
+    let mut sites = SITES.lock().unwrap_or_else(|e| e.into_inner());
+    if !sites.contains(&site) {
+        sites.push(site);
+    }
+    seen.0.store(name, Ordering::Relaxed);
+}
+
+/// Returns every site registered so far, with one entry for each type the
+/// site was called with, in the order they first ran.
+pub fn sites() -> Vec<Site> {
+    SITES.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Formats the registered sites, one per line, after a count of them; each
+/// line is a distinct erased type.
+pub fn report() -> String {
+    let sites = sites();
+    let mut out = String::new();
+    let _ = writeln!(out, "whiteout: {} erased types", sites.len());
+    for site in &sites {
+        let _ = writeln!(out, "  {}:{} ({}) erases {} to {}",
+            site.file, site.line, site.module, site.type_name, site.trait_name);
+    }
+    out
+}
@@ -0,0 +1,256 @@
+//! Generates the source of eraser functions, for build scripts which write
+//! them to files in `OUT_DIR` rather than expanding `eraser!`, and counts the
+//! erasure sites in a crate's source, for build scripts which report on them.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate whiteout;
+//!
+//! use whiteout::codegen::{eraser_source, Visibility};
+//!
+//! fn main() {
+//!     let source = eraser_source("erase_debug", "std::fmt::Debug", Visibility::Public);
+//!     assert_eq!(source, "\
+//! pub fn erase_debug<T: std::fmt::Debug>(val: T) -> impl std::fmt::Debug {
+//!     val
+//! }
+//! ");
+//! }
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// The visibility of a generated eraser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visibility {
+    /// No visibility, for an eraser private to its module.
+    Private,
+    /// `pub`
+    Public,
+    /// `pub(crate)`
+    Crate,
+}
+
+impl Visibility {
+    fn prefix(self) -> &'static str {
+        match self {
+            Visibility::Private => "",
+            Visibility::Public => "pub ",
+            Visibility::Crate => "pub(crate) ",
+        }
+    }
+}
+
+/// Returns the source of an eraser function with the given name for the
+/// given trait, the same function `eraser!(name, trait)` would create.
+///
+/// The name and trait are inserted as they are, so they must be a valid
+/// identifier and a valid bound.
+pub fn eraser_source(name: &str, trait_path: &str, vis: Visibility) -> String {
+    format!(
+        "{vis}fn {name}<T: {tr}>(val: T) -> impl {tr} {{\n    val\n}}\n",
+        vis = vis.prefix(),
+        name = name,
+        tr = trait_path,
+    )
+}
+
+/// The macros each of whose calls makes at least one new anonymous type.
+///
+/// The macros which box into trait objects, such as `boxerase!`, aren't
+/// included, since every call with the same trait gives the same type.
+pub const ERASING_MACROS: &[&str] = &[
+    "chainable_eraser", "const_erase", "const_eraser", "define_eraser_trait",
+    "erase", "erase_add", "erase_all", "erase_array", "erase_borrow",
+    "erase_checked", "erase_collect", "erase_deref", "erase_dyn_iter",
+    "erase_each", "erase_enum", "erase_fields", "erase_fmt_write",
+    "erase_from_fn", "erase_if", "erase_into_iter", "erase_iter",
+    "erase_logged", "erase_manual", "erase_map_keys", "erase_map_values",
+    "erase_mut", "erase_named", "erase_ok", "erase_opt", "erase_pair",
+    "erase_partition", "erase_ref", "erase_repeat",
+    "erase_result", "erase_sum", "erase_tagged", "erase_toggle", "erase_vec",
+    "erase_with_const", "erase_with_guard", "erase_with_type_name",
+    "erased_type", "eraser", "eraser_capturing", "eraser_fn", "eraser_for_each",
+    "eraser_impl", "eraser_method", "eraser_mut", "eraser_ref",
+    "eraser_with_default", "erasers", "from_erase", "layout_erase",
+    "let_erased", "loop_eraser", "pin_mut_erase", "reerase", "scoped_eraser",
+    "sealed_erase", "sealed_eraser", "spawn_erased", "try_eraser",
+    "tuple_erase",
+];
+
+/// A call of one of the `ERASING_MACROS`, found by `erasure_sites`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceSite {
+    /// The name of the macro called.
+    pub macro_name: &'static str,
+    /// The line of the call, counting from 1.
+    pub line: usize,
+}
+
+/// Returns the calls of erasing macros in the given Rust source, in order.
+///
+/// This reads the source as text rather than expanding it, so every call is
+/// found whether or not the code it's in ever runs, and comments, string
+/// literals and character literals are skipped. Calls made from inside other macros aren't found;
+/// nor are macros which were renamed on import.
+///
+/// # Examples
+///
+/// ```
+/// extern crate whiteout;
+///
+/// use whiteout::codegen::erasure_sites;
+///
+/// fn main() {
+///     let source = "
+///         // erase!(not, counted)
+///         let s = \"erase!(nor, this)\";
+///         let a = erase!(1, Debug);
+///         eraser!(erase_display, Display);
+///         let b = boxerase!(2, Debug);
+///     ";
+///     let names: Vec<_> = erasure_sites(source).iter().map(|s| (s.macro_name, s.line)).collect();
+///     assert_eq!(names, [("erase", 4), ("eraser", 5)]);
+/// }
+/// ```
+pub fn erasure_sites(source: &str) -> Vec<SourceSite> {
+    let bytes = source.as_bytes();
+    let mut sites = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\n' => line += 1,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    if bytes[i] == b'\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    match bytes[i] {
+                        b'\\' => i += 1,
+                        b'\n' => line += 1,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+            }
+            // A character literal, which may be a quote; not a lifetime
+            b'\'' if bytes.get(i + 1) == Some(&b'\\') => {
+                i += 3;
+                while i < bytes.len() && bytes[i] != b'\'' {
+                    i += 1;
+                }
+            }
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') => i += 2,
+            b if is_ident(b) => {
+                let start = i;
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+                let name = &source[start..i];
+                let mut j = i;
+                while j < bytes.len() && bytes[j] == b' ' {
+                    j += 1;
+                }
+                if bytes.get(j) == Some(&b'!') {
+                    j += 1;
+                    while j < bytes.len() && bytes[j] == b' ' {
+                        j += 1;
+                    }
+                    if let Some(b'(') | Some(b'[') | Some(b'{') = bytes.get(j) {
+                        if let Some(macro_name) = ERASING_MACROS.iter().find(|&&m| m == name) {
+                            sites.push(SourceSite { macro_name, line });
+                        }
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    sites
+}
+
+fn is_ident(b: u8) -> bool {
+    b == b'_' || b.is_ascii_alphanumeric()
+}
+
+/// Finds the erasure sites in every `.rs` file under `src_dir` and writes a
+/// report of them to `whiteout-bloat.txt` in `out_dir`, returning how many
+/// there are.
+///
+/// This is meant for build scripts, with `src_dir` the crate's `src` and
+/// `out_dir` its `OUT_DIR`. The report has a line for each site, giving the
+/// file, the line and the macro; the count can be printed with
+/// `cargo:warning` to show it in the build output.
+///
+/// ```no_run
+/// // build.rs
+/// extern crate whiteout;
+///
+/// use std::env;
+/// use std::path::Path;
+///
+/// fn main() {
+///     let out_dir = env::var("OUT_DIR").unwrap();
+///     let sites = whiteout::codegen::write_bloat_report(Path::new("src"), Path::new(&out_dir)).unwrap();
+///     println!("cargo:warning={} erasure sites", sites);
+///     println!("cargo:rerun-if-changed=src");
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn write_bloat_report(src_dir: &Path, out_dir: &Path) -> io::Result<usize> {
+    let mut files = Vec::new();
+    find_sources(src_dir, &mut files)?;
+    files.sort();
+    let mut lines = String::new();
+    let mut count = 0;
+    for file in &files {
+        let source = fs::read_to_string(file)?;
+        for site in erasure_sites(&source) {
+            lines.push_str(&format!("{}:{} {}!\n", file.display(), site.line, site.macro_name));
+            count += 1;
+        }
+    }
+    let report = format!("whiteout: {} erasure sites\n{}", count, lines);
+    fs::write(out_dir.join("whiteout-bloat.txt"), report)?;
+    Ok(count)
+}
+
+#[cfg(feature = "std")]
+fn find_sources(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_sources(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
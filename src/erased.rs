@@ -0,0 +1,447 @@
+//! Containers for erased values.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "debug-typenames")]
+use core::fmt;
+use core::alloc::Layout;
+use core::mem::ManuallyDrop;
+use core::ops::DerefMut;
+use core::ops::Deref;
+
+/// A trait object that is either borrowed or owned, for APIs that sometimes
+/// own an erased value and sometimes only borrow one.
+///
+/// This plays the role of `Cow<'a, dyn Trait>`, which can't be used since
+/// trait objects don't implement `ToOwned`. It derefs to the trait object, so
+/// the trait's methods can be called the same way on either variant. Use
+/// `cow_erase!` to build one from a concrete value.
+///
+/// # Examples
+///
+/// ```
+/// extern crate whiteout;
+///
+/// use std::fmt::Display;
+/// use whiteout::erased::ErasedCow;
+///
+/// fn main() {
+///     let name = String::from("borrowed");
+///     let values: Vec<ErasedCow<dyn Display>> = vec![
+///         ErasedCow::borrowed(&name),
+///         ErasedCow::owned(Box::new(5)),
+///     ];
+///     let shown: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+///     assert_eq!(shown, ["borrowed", "5"]);
+///     assert!(values[0].is_borrowed());
+///     assert!(values[1].is_owned());
+/// }
+/// ```
+///
+#[cfg(feature = "alloc")]
+pub enum ErasedCow<'a, Tr: ?Sized + 'a> {
+    /// A borrowed trait object.
+    Borrowed(&'a Tr),
+    /// An owned, boxed trait object.
+    Owned(Box<Tr>),
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Tr: ?Sized + 'a> ErasedCow<'a, Tr> {
+    /// Wraps a borrowed trait object.
+    pub fn borrowed(val: &'a Tr) -> Self {
+        ErasedCow::Borrowed(val)
+    }
+
+    /// Wraps an owned trait object.
+    pub fn owned(val: Box<Tr>) -> Self {
+        ErasedCow::Owned(val)
+    }
+
+    /// Returns true if the value is borrowed.
+    pub fn is_borrowed(&self) -> bool {
+        match *self {
+            ErasedCow::Borrowed(_) => true,
+            ErasedCow::Owned(_) => false,
+        }
+    }
+
+    /// Returns true if the value is owned.
+    pub fn is_owned(&self) -> bool {
+        !self.is_borrowed()
+    }
+
+    /// Returns a reference to the trait object, whichever variant holds it.
+    pub fn get(&self) -> &Tr {
+        match *self {
+            ErasedCow::Borrowed(val) => val,
+            ErasedCow::Owned(ref val) => val,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Tr: ?Sized + 'a> Deref for ErasedCow<'a, Tr> {
+    type Target = Tr;
+
+    fn deref(&self) -> &Tr {
+        self.get()
+    }
+}
+
+/// A value that can be viewed as the trait object `Tr` and cloned into a new
+/// box. Implemented by the values made by `clone_erase!`; not usually
+/// implemented by hand.
+#[cfg(feature = "alloc")]
+pub trait CloneErase<Tr: ?Sized> {
+    /// Returns the value as a trait object.
+    fn get(&self) -> &Tr;
+
+    /// Clones the value into a new box.
+    fn clone_box(&self) -> Box<dyn CloneErase<Tr>>;
+}
+
+/// A boxed trait object that can be cloned, made by `clone_erase!`.
+///
+/// Trait objects can't require `Clone`, since it isn't object safe, so this
+/// keeps a way to clone the value alongside it. It derefs to the trait object,
+/// and values of different types erased to the same trait share one type, so
+/// they can be stored together.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use std::fmt::Debug;
+/// use whiteout::erased::CloneableErased;
+///
+/// fn main() {
+///     let values: Vec<CloneableErased<dyn Debug>> = vec![
+///         clone_erase!(1, Debug),
+///         clone_erase!("two", Debug),
+///     ];
+///     let copies = values.clone();
+///     assert_eq!(format!("{:?}", &*copies[1]), "\"two\"");
+/// }
+/// ```
+///
+#[cfg(feature = "alloc")]
+pub struct CloneableErased<Tr: ?Sized + 'static>(Box<dyn CloneErase<Tr>>);
+
+#[cfg(feature = "alloc")]
+impl<Tr: ?Sized + 'static> CloneableErased<Tr> {
+    /// Boxes a value that can be cloned and viewed as `Tr`.
+    pub fn new<V: CloneErase<Tr> + 'static>(val: V) -> Self {
+        CloneableErased(Box::new(val))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Tr: ?Sized + 'static> Clone for CloneableErased<Tr> {
+    fn clone(&self) -> Self {
+        CloneableErased(self.0.clone_box())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Tr: ?Sized + 'static> Deref for CloneableErased<Tr> {
+    type Target = Tr;
+
+    fn deref(&self) -> &Tr {
+        self.0.get()
+    }
+}
+
+/// A boxed trait object which derefs to the trait object, made by
+/// `deref_erase!`.
+///
+/// This is a `Box<dyn Tr>` under a name of its own, so it can be given
+/// methods and trait impls which `Box` can't, while calls to the trait's
+/// methods work through `Deref` just the same. Being generic over the trait
+/// object, it's also a nameable type for generic functions whose callers
+/// choose the trait, where `impl Trait` can't be a type parameter.
+///
+/// It converts from a `Box<Tr>`, but not from a plain value of a type
+/// implementing the trait: `From<T>` for any `T` that coerces to `Tr` would
+/// need `T: Unsize<Tr>`, which isn't stable, and `Tr` being a type parameter
+/// rather than a trait leaves nothing else to bound `T` by. Use
+/// `deref_erase!` to make one from a value.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use std::fmt::{Debug, Display};
+/// use whiteout::erased::DerefErased;
+///
+/// fn last<Tr: ?Sized>(items: Vec<DerefErased<Tr>>) -> Option<DerefErased<Tr>> {
+///     items.into_iter().last()
+/// }
+///
+/// fn shown() -> DerefErased<dyn Display> {
+///     deref_erase!("two", Display)
+/// }
+///
+/// fn main() {
+///     let a: DerefErased<dyn Display> = DerefErased::new(Box::new(5));
+///     assert_eq!((*a).to_string(), "5");
+///     assert_eq!(a.to_string(), "5");
+///
+///     assert_eq!(last(vec![a, shown()]).unwrap().to_string(), "two");
+///
+///     let boxed: Box<dyn Debug> = Box::new([3]);
+///     let debugged: Vec<DerefErased<dyn Debug>> = vec![boxed.into()];
+///     assert_eq!(format!("{:?}", &*last(debugged).unwrap()), "[3]");
+/// }
+/// ```
+///
+#[cfg(feature = "alloc")]
+pub struct DerefErased<Tr: ?Sized>(Box<Tr>);
+
+#[cfg(feature = "alloc")]
+impl<Tr: ?Sized> DerefErased<Tr> {
+    /// Wraps a boxed trait object.
+    pub fn new(val: Box<Tr>) -> Self {
+        DerefErased(val)
+    }
+
+    /// Unwraps the boxed trait object.
+    pub fn into_inner(self) -> Box<Tr> {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Tr: ?Sized> Deref for DerefErased<Tr> {
+    type Target = Tr;
+
+    fn deref(&self) -> &Tr {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Tr: ?Sized> DerefMut for DerefErased<Tr> {
+    fn deref_mut(&mut self) -> &mut Tr {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Tr: ?Sized> From<Box<Tr>> for DerefErased<Tr> {
+    fn from(val: Box<Tr>) -> Self {
+        DerefErased(val)
+    }
+}
+
+/// An erased value along with the name of the type it had before erasure,
+/// made by `erase_with_type_name!` when the `debug-typenames` feature is on.
+///
+/// It derefs to the erased value, so the trait's methods can be called on it
+/// directly, and it formats like the erased value with `Debug` and `Display`.
+#[cfg(feature = "debug-typenames")]
+pub struct TypeNamed<T> {
+    value: T,
+    type_name: &'static str,
+}
+
+#[cfg(feature = "debug-typenames")]
+impl<T> TypeNamed<T> {
+    /// Wraps a value, recording the name of its type.
+    pub fn new(value: T) -> Self {
+        TypeNamed {
+            value,
+            type_name: core::any::type_name::<T>(),
+        }
+    }
+
+    /// Passes the value through the given function, such as an eraser,
+    /// keeping the recorded type name.
+    pub fn map<U, F>(self, f: F) -> TypeNamed<U>
+        where F: FnOnce(T) -> U
+    {
+        TypeNamed {
+            value: f(self.value),
+            type_name: self.type_name,
+        }
+    }
+
+    /// Returns the name of the type the value had when it was wrapped.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Unwraps the value, discarding the type name.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+#[cfg(feature = "debug-typenames")]
+impl<T> Deref for TypeNamed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+#[cfg(feature = "debug-typenames")]
+impl<T> DerefMut for TypeNamed<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+#[cfg(feature = "debug-typenames")]
+impl<T: fmt::Debug> fmt::Debug for TypeNamed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg(feature = "debug-typenames")]
+impl<T: fmt::Display> fmt::Display for TypeNamed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// An erased value stored inline along with the `Layout` of its type, made by
+/// `layout_erase!`.
+///
+/// The layout is captured when the value is wrapped, so code such as a pool
+/// or arena can size and align storage for the value without knowing its
+/// type. It derefs to the erased value, which in turn coerces to a trait
+/// object for the trait it was erased to.
+pub struct LayoutErased<T> {
+    value: T,
+    layout: Layout,
+}
+
+impl<T> LayoutErased<T> {
+    /// Wraps a value, recording the layout of its type.
+    pub fn new(value: T) -> Self {
+        LayoutErased {
+            value,
+            layout: Layout::new::<T>(),
+        }
+    }
+
+    /// Returns the layout of the type the value had when it was wrapped.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Unwraps the value, discarding the layout.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for LayoutErased<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for LayoutErased<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// Either an erased value or a value of its concrete type, made by
+/// `erase_toggle!` when its flag isn't a literal.
+///
+/// Which variant is made is decided by the flag, but the type has to be the
+/// same whichever way the flag goes, so it holds both possibilities. When the
+/// flag is a constant, the branch that isn't taken is optimized out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Toggled<E, T> {
+    /// The value, erased.
+    Erased(E),
+    /// The value, as its concrete type.
+    Concrete(T),
+}
+
+impl<E, T> Toggled<E, T> {
+    /// Returns true if the value was erased.
+    pub fn is_erased(&self) -> bool {
+        match *self {
+            Toggled::Erased(_) => true,
+            Toggled::Concrete(_) => false,
+        }
+    }
+
+    /// Returns the concrete value, if it wasn't erased.
+    pub fn concrete(self) -> Option<T> {
+        match self {
+            Toggled::Erased(_) => None,
+            Toggled::Concrete(val) => Some(val),
+        }
+    }
+}
+
+/// An erased value which runs a closure when it is dropped, made by
+/// `erase_with_guard!`.
+///
+/// It derefs to the erased value, so the trait's methods can be called on it
+/// directly. The closure runs once, after the value itself has been dropped,
+/// even if the value's destructor panics.
+pub struct Guarded<T, F: FnOnce()> {
+    value: ManuallyDrop<T>,
+    on_drop: ManuallyDrop<F>,
+}
+
+impl<T, F: FnOnce()> Guarded<T, F> {
+    /// Wraps a value, running `on_drop` when the wrapper is dropped.
+    pub fn new(value: T, on_drop: F) -> Self {
+        Guarded {
+            value: ManuallyDrop::new(value),
+            on_drop: ManuallyDrop::new(on_drop),
+        }
+    }
+}
+
+impl<T, F: FnOnce()> Deref for Guarded<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, F: FnOnce()> DerefMut for Guarded<T, F> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T, F: FnOnce()> Drop for Guarded<T, F> {
+    fn drop(&mut self) {
+        // Neither field is used again after being taken here. The closure is
+        // taken into a guard of its own first, so that it still runs while
+        // unwinding if the value's destructor panics.
+        unsafe {
+            let _on_drop = OnDrop(ManuallyDrop::new(ManuallyDrop::take(&mut self.on_drop)));
+            ManuallyDrop::drop(&mut self.value);
+        }
+    }
+}
+
+/// Runs a closure when dropped.
+struct OnDrop<F: FnOnce()>(ManuallyDrop<F>);
+
+impl<F: FnOnce()> Drop for OnDrop<F> {
+    fn drop(&mut self) {
+        // The closure is taken only here, and self isn't used again
+        unsafe { ManuallyDrop::take(&mut self.0)() }
+    }
+}
@@ -0,0 +1,46 @@
+//! Erased values whose drop is left to the caller, for code that hands them
+//! across a boundary which decides when they are freed.
+//!
+//! `erase_manual!` erases a value into a `ManuallyDrop`, so Rust never drops
+//! it on its own. It must be passed to `drop_erased` once it is no longer
+//! needed; otherwise it is leaked, and its `Drop` impl never runs.
+
+use core::mem::ManuallyDrop;
+
+/// Drops a value made by `erase_manual!`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::cell::Cell;
+/// use whiteout::ffi::drop_erased;
+///
+/// #[derive(Debug)]
+/// struct Counted<'a>(&'a Cell<u32>);
+///
+/// impl<'a> Drop for Counted<'a> {
+///     fn drop(&mut self) {
+///         self.0.set(self.0.get() + 1);
+///     }
+/// }
+///
+/// let drops = Cell::new(0);
+/// let handle = erase_manual!(Counted(&drops), std::fmt::Debug);
+/// assert_eq!(drops.get(), 0);
+/// drop_erased(handle);
+/// assert_eq!(drops.get(), 1);
+///
+/// // Without `drop_erased`, the value is leaked
+/// {
+///     let _leaked = erase_manual!(Counted(&drops), std::fmt::Debug);
+/// }
+/// assert_eq!(drops.get(), 1);
+///# }
+/// ```
+///
+pub fn drop_erased<T>(handle: ManuallyDrop<T>) {
+    drop(ManuallyDrop::into_inner(handle));
+}
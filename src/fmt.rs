@@ -0,0 +1,62 @@
+//! Ready-made erasers for the formatting traits, the most common traits to
+//! erase to.
+//!
+//! Each is the function `eraser!` would make. As with any eraser, values of
+//! the same type come out with the same anonymous type, so they can be kept
+//! together, while values of different types don't.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate whiteout;
+//!
+//! use whiteout::fmt::{erase_debug, erase_display, erase_lower_hex};
+//!
+//! fn main() {
+//!     let names = vec![erase_display("a"), erase_display("b")];
+//!     let shown: Vec<String> = vec![
+//!         format!("{}", names[0]),
+//!         format!("{:?}", erase_debug(Some(1.5))),
+//!         format!("{:x}", erase_lower_hex(255u8)),
+//!         format!("{}", names[1]),
+//!     ];
+//!     assert_eq!(shown, ["a", "Some(1.5)", "ff", "b"]);
+//! }
+//! ```
+
+use core::fmt;
+
+/// Erases any value to an anonymous type that is `impl Display`.
+pub fn erase_display<T: fmt::Display>(val: T) -> impl fmt::Display {
+    val
+}
+
+/// Erases any value to an anonymous type that is `impl Debug`.
+pub fn erase_debug<T: fmt::Debug>(val: T) -> impl fmt::Debug {
+    val
+}
+
+/// Erases any value to an anonymous type that is `impl Display + Debug`.
+pub fn erase_display_debug<T: fmt::Display + fmt::Debug>(val: T) -> impl fmt::Display + fmt::Debug {
+    val
+}
+
+/// Erases any value to an anonymous type that is `impl LowerHex`.
+pub fn erase_lower_hex<T: fmt::LowerHex>(val: T) -> impl fmt::LowerHex {
+    val
+}
+
+/// Erases any value to an anonymous type that is `impl UpperHex`.
+pub fn erase_upper_hex<T: fmt::UpperHex>(val: T) -> impl fmt::UpperHex {
+    val
+}
+
+/// Erases any value to an anonymous type that is `impl Octal`.
+pub fn erase_octal<T: fmt::Octal>(val: T) -> impl fmt::Octal {
+    val
+}
+
+/// Erases any value to an anonymous type that is `impl Binary`.
+pub fn erase_binary<T: fmt::Binary>(val: T) -> impl fmt::Binary {
+    val
+}
@@ -54,7 +54,190 @@
 //! }
 //! ```
 //!
+//! Values from two different erasures can't be used together.
 //!
+//! ```compile_fail,E0308
+//!# #[macro_use]
+//!# extern crate whiteout;
+//!# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+//!# impl<T> MyTrait for T
+//!#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+//!# fn main() {
+//! let a = erase!(10, MyTrait);
+//! let b = erase!(5, MyTrait);
+//! // a and b have different anonymous types
+//! let c = a + b;
+//!# }
+//! ```
+//!
+//! # Features
+//!
+//! `whiteout` works without the standard library. The `std` feature is on by
+//! default; with `--no-default-features`, the crate is `#![no_std]`, and the
+//! `alloc` feature enables the macros that need an allocator, such as
+//! `boxerase!`.
+//!
+//! The `codegen` feature adds the `codegen` module, for generating the source
+//! of erasers from build scripts.
+//!
+//! The `debug-typenames` feature makes `erase_with_type_name!` record the name
+//! of each value's type before erasure, for diagnostics.
+//!
+//! The `serde` feature adds `erase_serialize!`, for boxing values as
+//! `erased_serde::Serialize` trait objects. It doesn't pull in `erased_serde`
+//! itself; the crate using the macro depends on it directly.
+//!
+//! The `logging` feature makes `erase_logged!` log the name of each value's
+//! type with `log::trace!`. Like `serde`, it doesn't pull in `log`; the crate
+//! using the macro depends on it directly.
+//!
+//! Trait aliases, which are unstable, can be used as the trait like any other
+//! trait; the `nightly-trait-alias` feature runs the tests of this on a
+//! nightly compiler.
+//!
+//! The `bloat-report` feature adds the `bloat` module, a runtime tracer
+//! which records each eraser and the types it's called with as it runs, to
+//! show how many distinct erased types a program makes.
+//! `codegen::write_bloat_report` counts the erasure sites in a crate's source
+//! as it builds, from a build script.
+//!
+//! The `nightly-tait` feature adds `erased_type!`, which defines a type alias
+//! for an erased type, so it can be named. It needs a nightly compiler and
+//! `#![feature(type_alias_impl_trait)]` in the crate using it.
+//!
+//!
+
+// The examples spell out `fn main` so that they read like complete programs.
+#![allow(clippy::needless_doctest_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+extern crate whiteout_macros;
+
+#[cfg(feature = "bloat-report")]
+pub mod bloat;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+pub mod erased;
+pub mod ffi;
+pub mod fmt;
+#[cfg(feature = "alloc")]
+pub mod small;
+
+/// Items used by the macros, which must be reachable from crates that don't
+/// link `std`. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use core::any::{type_name, type_name_of_val, Any};
+    pub use core::array::from_fn as array_from_fn;
+    pub use core::cell::RefCell;
+    pub use core::iter::from_fn as iter_from_fn;
+    pub use core::convert::TryInto;
+    pub use core::fmt::Write as FmtWrite;
+    pub use core::convert::From;
+    pub use core::default::Default;
+    pub use core::iter::IntoIterator;
+    pub use core::ops::{Deref, DerefMut};
+    pub use core::pin::Pin;
+    pub use core::mem::{replace, ManuallyDrop};
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
+    pub use alloc::rc::Rc;
+    #[cfg(feature = "alloc")]
+    pub use alloc::sync::Arc;
+    #[cfg(feature = "alloc")]
+    pub use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    pub use std::collections::HashMap;
+    #[cfg(feature = "std")]
+    pub use std::sync::OnceLock;
+    #[cfg(feature = "std")]
+    pub use std::thread::{spawn, JoinHandle};
+}
+
+/// Attribute macros, which live in the `whiteout-macros` crate.
+///
+/// `#[erase(trait)]` erases the return type of a function into an anonymous
+/// type that is `impl Trait` for the given trait. The declared return type is
+/// still used to check the body, so every `return` and the tail expression
+/// are erased to the same type.
+///
+/// These can't be re-exported at the crate root, since `erase` would clash
+/// with the `erase!` macro.
+///
+/// # Examples
+///
+/// ```
+/// extern crate whiteout;
+///
+/// use whiteout::attr::erase;
+///
+/// #[erase(std::fmt::Debug)]
+/// fn pick(first: bool) -> Option<i32> {
+///     if first {
+///         return Some(1);
+///     }
+///     None
+/// }
+///
+/// fn main() {
+///     // Both return sites produce the same opaque type
+///     let both = [pick(true), pick(false)];
+///     assert_eq!(format!("{:?}", both), "[Some(1), None]");
+/// }
+/// ```
+///
+/// It works on methods too, including those implementing a trait method that
+/// returns `impl Trait`, so the body can be checked against a concrete type
+/// while the method keeps the signature the trait declares.
+///
+/// ```
+/// extern crate whiteout;
+///
+/// use whiteout::attr::erase;
+///
+/// trait Digits {
+///     fn digits(&self) -> impl Iterator<Item = u32>;
+/// }
+///
+/// impl Digits for u32 {
+///     #[erase(Iterator<Item = u32>)]
+///     fn digits(&self) -> std::vec::IntoIter<u32> {
+///         let mut digits: Vec<u32> = self.to_string().chars().filter_map(|c| c.to_digit(10)).collect();
+///         digits.reverse();
+///         digits.into_iter()
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(123.digits().collect::<Vec<_>>(), [3, 2, 1]);
+/// }
+/// ```
+///
+/// The body is run in a closure, which can't `.await` or be called in a
+/// const context, so `#[erase]` rejects `async fn` and `const fn` with a
+/// compile error. An async function can return an erased future instead,
+/// with `erase!(async move { .. }, Future<Output = T>)`.
+///
+/// ```compile_fail
+/// extern crate whiteout;
+///
+/// use whiteout::attr::erase;
+///
+/// #[erase(std::fmt::Debug)]
+/// async fn fetch() -> u32 {
+///     1
+/// }
+///# fn main() {}
+/// ```
+///
+pub mod attr {
+    pub use whiteout_macros::erase;
+}
 
 
 /// `eraser!(name, trait)` creates a function with the given identifier that 
@@ -95,47 +278,5145 @@
 /// }
 /// ```
 ///
+/// The generated function is private by default. A visibility specifier
+/// may be given before the name, as in `eraser!(pub name, trait)`, to
+/// expose it from a module.
 ///
-#[macro_export]
-macro_rules! eraser {
-    ($name:ident, $($tr:tt)*) => {
-            // This function takes any type implementing T and returns impl T
-            fn $name<T: $($tr)*>(val: T) -> impl $($tr)* {
-                // Do nothing to the value
-                val
-            }
-    }
-}
-
-
-/// `erase!(value, trait)` turns a value of any type that implements trait into 
-/// an erasted type which is `impl Trait` for that trait.
-/// 
-/// # Examples
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// mod erasers {
+///     eraser!(pub erase_debug, std::fmt::Debug);
+///     eraser!(pub(crate) erase_display, std::fmt::Display);
+/// }
 ///
+/// mod users {
+///     pub fn show() -> String {
+///         let a = super::erasers::erase_debug(10);
+///         let b = super::erasers::erase_display("hello");
+///         format!("{:?} {}", a, b)
+///     }
+/// }
 ///
+/// fn main() {
+///     assert_eq!(users::show(), "10 hello");
+/// }
 /// ```
-///# #[macro_use]
-///# extern crate whiteout;
-///# fn main() {
-/// let a = erase!(10, std::ops::Add<i64, Output=i64>);
-/// let b = erase!(5, std::ops::Add<i64, Output=i64>);
-/// assert_eq!(a + 10, 20);
-/// assert_eq!(b + 10, 15);
-/// // This fails, the types are opaque
-/// // assert_eq!(a + b, 15);
-///# }
+///
+/// Any visibility can be used, including `pub(in path)`, which limits the
+/// eraser to the given ancestor module.
+///
 /// ```
+/// #[macro_use]
+/// extern crate whiteout;
 ///
-#[macro_export]
-macro_rules! erase {
-    ($val:expr, $($tr:tt)*) => {
-        // Creates a block to operate in
-        {
-            eraser!(f, $($tr)*);
-            // Immediately use this function
-            f($val)
-        }
-    }
-}
-
+/// mod outer {
+///     pub mod inner {
+///         eraser!(pub(in crate::outer) erase_debug, std::fmt::Debug);
+///     }
+///
+///     pub fn show() -> String {
+///         format!("{:?}", inner::erase_debug(10))
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(outer::show(), "10");
+/// }
+/// ```
+///
+/// ```compile_fail,E0603
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// mod outer {
+///     pub mod inner {
+///         eraser!(pub(in crate::outer) erase_debug, std::fmt::Debug);
+///     }
+/// }
+///
+/// fn main() {
+///     // Outside of `outer`, so the eraser is private
+///     let a = outer::inner::erase_debug(10);
+/// }
+/// ```
+///
+/// Attributes given before the name are placed on the generated function.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(#[inline(always)] fast_erase, std::ops::Add<i64, Output=i64>);
+///
+/// fn main() {
+///     let a = fast_erase(10);
+///     assert_eq!(a + 10, 20);
+/// }
+/// ```
+///
+/// `#[track_caller]` is accepted too, though since the eraser itself never
+/// panics it changes little: a panic inside a trait method is still reported
+/// at that method's impl, which is where it happens.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(#[track_caller] tracked_erase, std::fmt::Debug);
+///
+/// fn main() {
+///     let a = tracked_erase(10);
+///     assert_eq!(format!("{:?}", a), "10");
+/// }
+/// ```
+///
+/// `#[must_use]`, with or without a message, makes ignoring the erased value
+/// a warning at the call site.
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(#[must_use = "the guard is dropped at once"] erase_guard, std::fmt::Debug);
+///
+/// fn main() {
+///     erase_guard(10);
+/// }
+/// ```
+///
+/// This includes doc comments, written either as `///` or as `#[doc]`, so
+/// that public erasers show up documented in rustdoc.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// pub mod erasers {
+///     eraser!(
+///         /// Erases values into `Display`.
+///         pub erase_display, std::fmt::Display
+///     );
+///     eraser!(#[doc = "Erases values into `Debug`."] pub erase_debug, std::fmt::Debug);
+/// }
+///
+/// fn main() {
+///     let a = erasers::erase_display(1);
+///     let b = erasers::erase_debug(2);
+///     assert_eq!(format!("{} {:?}", a, b), "1 2");
+/// }
+/// ```
+///
+/// Lifetime parameters may be declared after the name, so that values which
+/// borrow can be erased. They must appear in the trait for the erased type to
+/// capture them.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(erase_bytes<'a>, Iterator<Item = &'a u8> + 'a);
+///
+/// fn evens<'a>(data: &'a [u8]) -> impl Iterator<Item = &'a u8> + 'a {
+///     erase_bytes(data.iter().filter(|x| *x % 2 == 0))
+/// }
+///
+/// fn main() {
+///     let data = vec![1u8, 2, 3, 4];
+///     // The erased iterator outlives the call that created it
+///     let it = evens(&data);
+///     assert_eq!(it.cloned().collect::<Vec<u8>>(), [2, 4]);
+/// }
+/// ```
+///
+/// The same goes for traits that take a lifetime parameter themselves.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Parser<'a> {
+///     fn parse(&self, input: &'a str) -> Option<&'a str>;
+/// }
+///
+/// struct Word;
+/// struct Prefix(&'static str);
+///
+/// impl<'a> Parser<'a> for Word {
+///     fn parse(&self, input: &'a str) -> Option<&'a str> {
+///         input.split_whitespace().next()
+///     }
+/// }
+///
+/// impl<'a> Parser<'a> for Prefix {
+///     fn parse(&self, input: &'a str) -> Option<&'a str> {
+///         if input.starts_with(self.0) { Some(&input[..self.0.len()]) } else { None }
+///     }
+/// }
+///
+/// // Generates fn erase_parser<'a, T: Parser<'a>>(val: T) -> impl Parser<'a>
+/// eraser!(erase_parser<'a>, Parser<'a>);
+///
+/// fn main() {
+///     let input = String::from("hello world");
+///     let word = erase_parser(Word);
+///     let prefix = erase_parser(Prefix("he"));
+///     assert_eq!(word.parse(&input), Some("hello"));
+///     assert_eq!(prefix.parse(&input), Some("he"));
+///     assert_eq!(prefix.parse("world"), None);
+/// }
+/// ```
+///
+/// On Rust 1.82 and later, the trait may end with a `+ use<...>` list to say
+/// exactly which generic parameters the erased type captures. The list is
+/// left out of the bound on the value. Type parameters, including the `T` of
+/// the value, must always be listed.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// // Generates fn erase_bytes<'a, T: Iterator<Item = &'a u8>>(val: T)
+/// //     -> impl Iterator<Item = &'a u8> + use<'a, T>
+/// eraser!(erase_bytes<'a>, Iterator<Item = &'a u8> + use<'a, T>);
+///
+/// fn evens<'a>(data: &'a [u8]) -> impl Iterator<Item = &'a u8> + 'a {
+///     erase_bytes(data.iter().filter(|x| *x % 2 == 0))
+/// }
+///
+/// fn main() {
+///     let data = vec![1u8, 2, 3, 4];
+///     let it = evens(&data);
+///     assert_eq!(it.cloned().collect::<Vec<u8>>(), [2, 4]);
+/// }
+/// ```
+///
+/// Several lifetimes can be given, for values that borrow from more than one
+/// place. With `+ 'a + 'b`, the erased value can only be used while both
+/// borrows are alive.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(erase_view<'a, 'b>, Iterator<Item = &'a u8> + 'a + 'b);
+///
+/// fn total<'a, 'b>(head: &'a [u8], tail: &'b [u8]) -> u32 {
+///     let view = erase_view(head.iter().chain(tail));
+///     view.map(|&b| u32::from(b)).sum()
+/// }
+///
+/// fn main() {
+///     let head = vec![1, 2];
+///     let sum = {
+///         let tail = vec![3, 4];
+///         total(&head, &tail)
+///     };
+///     assert_eq!(sum, 10);
+/// }
+/// ```
+///
+/// Type parameters may be declared the same way, for traits which are
+/// themselves generic. They come before the type of the value in the
+/// generated function's generics.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// // Generates fn into_erase<U, T: Into<U>>(val: T) -> impl Into<U>
+/// eraser!(into_erase<U>, Into<U>);
+///
+/// fn main() {
+///     let a = into_erase(10i32);
+///     let b: i64 = a.into();
+///     assert_eq!(b, 10);
+/// }
+/// ```
+///
+/// Their bounds may be generic too.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(erase_items<U: Into<u64>>, Iterator<Item = U>);
+///
+/// fn main() {
+///     let items = erase_items(vec![1u8, 2, 3].into_iter());
+///     assert_eq!(items.map(u64::from).sum::<u64>(), 6);
+/// }
+/// ```
+///
+/// So may const parameters, for traits generic over a constant. Each constant
+/// gives a different erased type.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Buffer<const N: usize> {
+///     fn total(&self) -> u32;
+/// }
+///
+/// impl<const N: usize> Buffer<N> for [u8; N] {
+///     fn total(&self) -> u32 {
+///         self.iter().map(|&b| u32::from(b)).sum()
+///     }
+/// }
+///
+/// // Generates fn erase_buf<const N: usize, T: Buffer<N>>(val: T) -> impl Buffer<N>
+/// eraser!(erase_buf<const N: usize>, Buffer<N>);
+///
+/// fn main() {
+///     let a = erase_buf([1, 2]);
+///     let b = erase_buf([1, 2, 3]);
+///     assert_eq!(a.total() + b.total(), 9);
+/// }
+/// ```
+///
+/// The parameter of the generated function is named `val`. Another name can
+/// be given in parentheses after the function's name, which reads better in
+/// the documentation of public erasers. It makes no difference to callers.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// // Generates fn erase_widget<T: Debug>(widget: T) -> impl Debug
+/// eraser!(erase_widget(widget), std::fmt::Debug);
+///
+/// fn main() {
+///     let a = erase_widget("gear");
+///     assert_eq!(format!("{:?}", a), "\"gear\"");
+/// }
+/// ```
+///
+/// The trait may be followed by a `where` clause constraining the generic
+/// parameter of the generated function, which is always named `T`. Every
+/// token between the comma and `where` is used as the trait, both as the
+/// bound on `T` and in the `impl Trait` return type; every token after
+/// `where` is emitted verbatim as the function's where clause.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(erase_iter, Iterator where T::Item: Copy + std::fmt::Debug, T: Clone);
+///
+/// fn main() {
+///     let a = erase_iter(vec![1, 2, 3].into_iter());
+///     assert_eq!(a.count(), 3);
+/// }
+/// ```
+///
+/// Rust has no `where T::Item == X` bound; an associated type is made equal
+/// to a type by binding it in the trait, as in `Iterator<Item = u8>`. Bound
+/// in the trait, it constrains `T` and is part of the `impl Trait` return
+/// type, so callers see the items as `u8`. Bound only in the where clause, it
+/// constrains `T` but the return type hides it.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(erase_bytes, Iterator<Item = u8> where T: ExactSizeIterator<Item = u8>);
+///
+/// fn main() {
+///     let bytes = erase_bytes(b"hi".to_vec().into_iter());
+///     let sum: u8 = bytes.map(|b| b - b'a').sum();
+///     assert_eq!(sum, 7 + 8);
+/// }
+/// ```
+///
+/// Since it expands to a single `fn`, `eraser!` can also be used inside an
+/// `impl` block to define an associated function, or inside a trait to give
+/// it a provided one. Trait items can't have a visibility.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// struct Erasers;
+///
+/// impl Erasers {
+///     eraser!(pub erase, std::fmt::Debug);
+/// }
+///
+/// trait Factory {
+///     eraser!(make, std::fmt::Display);
+/// }
+///
+/// impl Factory for Erasers {}
+///
+/// fn main() {
+///     let a = Erasers::erase(1);
+///     let b = <Erasers as Factory>::make("two");
+///     assert_eq!(format!("{:?} {}", a, b), "1 two");
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser {
+    // Allow a trailing comma in the generics
+    (@generics [$($head:tt)*] [$($gen:tt)*] [] , > $($rest:tt)*) => {
+        $crate::eraser!(@params [$($head)*] [$($gen)* ,] $($rest)*);
+    };
+    // Found the end of the generics
+    (@generics [$($head:tt)*] [$($gen:tt)*] [] > $($rest:tt)*) => {
+        $crate::eraser!(@params [$($head)*] [$($gen)* ,] $($rest)*);
+    };
+    // `>>` is a single token; it closes a bound's generics and then either
+    // the function's or another level of the bound's
+    (@generics [$($head:tt)*] [$($gen:tt)*] [<] >> $($rest:tt)*) => {
+        $crate::eraser!(@params [$($head)*] [$($gen)* > ,] $($rest)*);
+    };
+    (@generics [$($head:tt)*] [$($gen:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::eraser!(@generics [$($head)*] [$($gen)* >>] [$($depth)*] $($rest)*);
+    };
+    // Angle brackets inside the generics, as in `U: Into<u8>`
+    (@generics [$($head:tt)*] [$($gen:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::eraser!(@generics [$($head)*] [$($gen)* >] [$($depth)*] $($rest)*);
+    };
+    (@generics [$($head:tt)*] [$($gen:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::eraser!(@generics [$($head)*] [$($gen)* <] [< $($depth)*] $($rest)*);
+    };
+    // Move one token of the generics into the accumulator
+    (@generics [$($head:tt)*] [$($gen:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::eraser!(@generics [$($head)*] [$($gen)* $next] [$($depth)*] $($rest)*);
+    };
+    // An explicit name for the parameter; the trait follows
+    (@params [$($head:tt)*] [$($gen:tt)*] ($param:ident), $($tr:tt)*) => {
+        $crate::eraser!(@split [$($head)*] [$($gen)*] [$param] [] $($tr)*);
+    };
+    (@params [$($head:tt)*] [$($gen:tt)*] , $($tr:tt)*) => {
+        $crate::eraser!(@split [$($head)*] [$($gen)*] [val] [] $($tr)*);
+    };
+    // Found the where clause; everything before it is the trait
+    (@split [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] where $($wh:tt)*) => {
+        $crate::eraser!(@emit [$($head)*] [$($gen)*] [$param] [$($tr)*] [] [$($wh)*]);
+    };
+    // A trailing comma ends the trait
+    (@split [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] ,) => {
+        $crate::eraser!(@emit [$($head)*] [$($gen)*] [$param] [$($tr)*] [] []);
+    };
+    // A capture list only applies to the return type, so it's kept apart
+    // from the trait
+    (@split [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] + use < > $($rest:tt)*) => {
+        $crate::eraser!(@captured [$($head)*] [$($gen)*] [$param] [$($tr)*] [+ use<>] $($rest)*);
+    };
+    (@split [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] + use < $($cap:tt),+ > $($rest:tt)*) => {
+        $crate::eraser!(@captured [$($head)*] [$($gen)*] [$param] [$($tr)*] [+ use<$($cap),+>] $($rest)*);
+    };
+    // Move one token of the trait into the accumulator
+    (@split [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::eraser!(@split [$($head)*] [$($gen)*] [$param] [$($tr)* $next] $($rest)*);
+    };
+    // Ran out of tokens without finding a where clause
+    (@split [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*]) => {
+        $crate::eraser!(@emit [$($head)*] [$($gen)*] [$param] [$($tr)*] [] []);
+    };
+    // The capture list ends the trait
+    (@captured [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] [$($cap:tt)*] $(,)?) => {
+        $crate::eraser!(@emit [$($head)*] [$($gen)*] [$param] [$($tr)*] [$($cap)*] []);
+    };
+    (@captured [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] [$($cap:tt)*] where $($wh:tt)*) => {
+        $crate::eraser!(@emit [$($head)*] [$($gen)*] [$param] [$($tr)*] [$($cap)*] [$($wh)*]);
+    };
+    (@emit [$($head:tt)*] [$($gen:tt)*] [$param:ident] [$($tr:tt)*] [$($cap:tt)*] [$($wh:tt)*]) => {
+            // This function takes any type implementing T and returns impl T.
+            // A where clause about T bounds it in a second place, on purpose.
+            #[allow(clippy::multiple_bound_locations)]
+            $($head)* <$($gen)* T: $($tr)*>($param: T) -> impl $($tr)* $($cap)* where $($wh)* {
+                $crate::__register_eraser!([$($head)*] [$($tr)*]);
+                // Do nothing to the value
+                $param
+            }
+    };
+    // Build the head of the function from its qualifiers, attributes,
+    // visibility and name
+    (@fn [$($qual:tt)*] [$($pre:tt)*] $name:ident < $($rest:tt)*) => {
+        $crate::eraser!(@generics [$($pre)* $($qual)* fn $name] [] [] $($rest)*);
+    };
+    (@fn [$($qual:tt)*] [$($pre:tt)*] $name:ident $($rest:tt)*) => {
+        $crate::eraser!(@params [$($pre)* $($qual)* fn $name] [] $($rest)*);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident $($rest:tt)*) => {
+        $crate::eraser!(@fn [] [$(#[$attr])* $vis] $name $($rest)*);
+    };
+}
+
+// Registers the site of an eraser, with the type of its argument `T`, with
+// the `bloat` module. The head of the function is searched for `const`,
+// since a const fn can't register itself.
+#[cfg(feature = "bloat-report")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_eraser {
+    ([const $($rest:tt)*] [$($tr:tt)*]) => {};
+    ([fn $($rest:tt)*] [$($tr:tt)*]) => {
+        // Shared by every type the eraser is called with
+        static SEEN: $crate::bloat::Seen = $crate::bloat::Seen::new();
+        $crate::bloat::register(&SEEN, $crate::bloat::Site {
+            trait_name: stringify!($($tr)*),
+            module: module_path!(),
+            file: file!(),
+            line: line!(),
+            type_name: $crate::__private::type_name::<T>(),
+        });
+    };
+    ([$first:tt $($rest:tt)*] [$($tr:tt)*]) => {
+        $crate::__register_eraser!([$($rest)*] [$($tr)*])
+    };
+}
+
+#[cfg(not(feature = "bloat-report"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_eraser {
+    ($($any:tt)*) => {};
+}
+
+/// `erase!(value, trait)` turns a value of any type that implements trait into 
+/// an erasted type which is `impl Trait` for that trait.
+///
+/// The value is passed to the eraser like any function argument, so it is
+/// moved into the erased result (or copied, if it is `Copy`). Use
+/// `erase_borrow!` or `erase_ref!` to erase a value without moving it.
+/// 
+/// # Examples
+///
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(10, std::ops::Add<i64, Output=i64>);
+/// let b = erase!(5, std::ops::Add<i64, Output=i64>);
+/// assert_eq!(a + 10, 20);
+/// assert_eq!(b + 10, 15);
+/// // This fails, the types are opaque
+/// // assert_eq!(a + b, 15);
+///# }
+/// ```
+///
+/// `erase!` expands to a block expression, so it can be used anywhere an
+/// expression can, including in array literals, struct fields, function
+/// arguments and match guards. The block holds the eraser function, since
+/// only a function can produce an anonymous `impl Trait` type.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// struct Labelled<T> {
+///     label: T,
+/// }
+///
+/// fn show<A: std::fmt::Debug, B: std::fmt::Debug>(a: A, b: B) -> String {
+///     format!("{:?} {:?}", a, b)
+/// }
+///
+/// let array = [erase!(1, std::fmt::Debug)];
+/// let labelled = Labelled { label: erase!("a", std::fmt::Debug) };
+/// let shown = show(erase!(1, std::fmt::Debug), erase!(2, std::fmt::Debug));
+/// let matched = match Some(3) {
+///     Some(x) if erase!(x, PartialEq<i32>) == 3 => true,
+///     _ => false,
+/// };
+/// assert_eq!(format!("{:?} {:?}", array, labelled.label), "[1] \"a\"");
+/// assert_eq!(shown, "1 2");
+/// assert!(matched);
+///# }
+/// ```
+///
+/// If the value doesn't implement the trait, the error points at the value
+/// itself, naming its type and the missing trait; here, rustc reports
+/// ``cannot add `i64` to `&str` `` at `"hi"`.
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!("hi", std::ops::Add<i64, Output=i64>);
+///# }
+/// ```
+///
+/// Several traits can be combined with `+`, and the methods of all of them
+/// remain available on the erased value.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(vec![1, 2], std::fmt::Debug + Clone);
+/// let b = a.clone();
+/// assert_eq!(format!("{:?} {:?}", a, b), "[1, 2] [1, 2]");
+///# }
+/// ```
+///
+/// With `Hash + Eq`, erased values can be used as map keys, and hash exactly
+/// like the values they came from. A key for looking up an entry has to have
+/// the same erased type, so it must come from the same eraser.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use std::collections::HashMap;
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::{Hash, Hasher};
+///
+/// eraser!(erase_key, Hash + Eq + std::fmt::Debug);
+///
+/// fn hash_of<T: Hash>(val: &T) -> u64 {
+///     let mut hasher = DefaultHasher::new();
+///     val.hash(&mut hasher);
+///     hasher.finish()
+/// }
+///
+/// fn main() {
+///     assert_eq!(hash_of(&erase_key("id")), hash_of(&"id"));
+///
+///     let mut map = HashMap::new();
+///     map.insert(erase_key("id"), 7);
+///     assert_eq!(map.get(&erase_key("id")), Some(&7));
+///     assert_eq!(map.get(&erase_key("other")), None);
+/// }
+/// ```
+///
+/// The value may be any expression, including method chains, struct
+/// literals, closures, and `if` or `match` expressions, with no extra
+/// parentheses. Commas inside the value are fine as long as they are part of
+/// its syntax, such as the parameters of a closure.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// #[derive(Debug, Default)]
+/// struct Point { x: i32, y: i32 }
+///
+/// let origin = Point::default();
+/// let a = erase!(vec![3, 1, 2].into_iter().rev().collect::<Vec<_>>(), std::fmt::Debug);
+/// let b = erase!(Point { x: 1, ..origin }, std::fmt::Debug);
+/// let c = erase!(|x, y| x * y, Fn(i32, i32) -> i32);
+/// let d = erase!(if c(1, 2) > 1 { "big" } else { "small" }, std::fmt::Debug);
+/// let e = erase!(match c(2, 2) { 4 => Some(4), _ => None }, std::fmt::Debug);
+/// assert_eq!(format!("{:?}", a), "[2, 1, 3]");
+/// assert_eq!(format!("{:?}", b), "Point { x: 1, y: 0 }");
+/// assert_eq!(c(3, 4), 12);
+/// assert_eq!(format!("{:?} {:?}", d, e), "\"big\" Some(4)");
+///# }
+/// ```
+///
+/// Several values can be erased through one eraser by giving them in
+/// parentheses followed by `=>` and the trait, as in
+/// `erase!((a, b) => Trait)`. This is the same as `tuple_erase!`: the result
+/// is a tuple, and values of the same type come out with the same anonymous
+/// type, so they can be used together.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let (a, b, c) = erase!((1, 2, 3) => MyTrait);
+/// assert_eq!(a + b + c, 6.into());
+///
+/// // A tuple followed by a comma is still a single value
+/// let pair = erase!((1, 2), std::fmt::Debug);
+/// assert_eq!(format!("{:?}", pair), "(1, 2)");
+///# }
+/// ```
+///
+/// A trailing comma is allowed after the trait, here and in `eraser!`, which
+/// suits invocations split over several lines.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(
+///     10,
+///     std::ops::Add<i64, Output=i64>,
+/// );
+/// eraser!(erase_debug, std::fmt::Debug,);
+/// assert_eq!(a + 10, 20);
+/// assert_eq!(format!("{:?}", erase_debug(1)), "1");
+///# }
+/// ```
+///
+/// Default type parameters apply as they do anywhere else, so operator
+/// traits can be written without their right-hand side, which defaults to
+/// the erased type itself.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// eraser!(erase_sum, std::ops::Add<Output=i32>);
+/// assert_eq!(erase_sum(1) + erase_sum(2), 3);
+///
+/// // Nothing is known about the sum when `Output` is left out too
+/// eraser!(erase_add, std::ops::Add);
+/// let _sum = erase_add(1) + erase_add(2);
+///# }
+/// ```
+///
+/// Leaving out the right-hand side and `Output` is a common mistake when the
+/// erased value is meant to be added to concrete values. The macro can't tell
+/// this apart from a deliberate bare `Add`, so the errors come from the use
+/// instead: the right-hand side must be the erased type, and the result is of
+/// an unknown type. The compiler suggests the missing `Output` binding; the
+/// right-hand side has to be given too, as in `Add<i32, Output = i32>`.
+///
+/// ```compile_fail,E0308
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(10, std::ops::Add);
+/// let b: i32 = a + 10;
+///# }
+/// ```
+///
+/// Auto traits are leaked through `impl Trait`, so an erased value is `Send`
+/// or `Sync` whenever the original is. To require them, add them to the
+/// trait; values that lack them are then rejected.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(vec![1, 2, 3], std::fmt::Debug + Send + Sync);
+/// let shown = std::thread::spawn(move || format!("{:?}", a)).join().unwrap();
+/// assert_eq!(shown, "[1, 2, 3]");
+///# }
+/// ```
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// // Rc is not Send
+/// let a = erase!(std::rc::Rc::new(1), std::fmt::Debug + Send);
+///# }
+/// ```
+///
+/// Leaking works both ways: a value that isn't `Send` gives an erased value
+/// that isn't either, even though the trait doesn't mention it.
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(std::rc::Rc::new(1), std::fmt::Debug);
+/// std::thread::spawn(move || format!("{:?}", a));
+///# }
+/// ```
+///
+/// Values which are already trait objects can be erased like any other, as
+/// long as the boxed or borrowed trait object implements the trait.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let boxed: Box<dyn std::fmt::Debug> = Box::new(vec![1, 2]);
+/// let a = erase!(boxed, std::fmt::Debug);
+/// assert_eq!(format!("{:?}", a), "[1, 2]");
+///
+/// let borrowed: &dyn std::fmt::Display = &"hi";
+/// let b = erase!(borrowed, std::fmt::Display + Copy);
+/// assert_eq!(format!("{} {}", b, b), "hi hi");
+///# }
+/// ```
+///
+/// `Copy` is not an auto trait, so an erased value can only be used more than
+/// once if `Copy` is part of the trait.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// fn take<T: std::ops::Add<i32, Output=i32>>(val: T) -> i32 { val + 0 }
+///
+/// let a = erase!(21, std::ops::Add<i32, Output=i32> + Copy);
+/// assert_eq!(take(a) + take(a), 42);
+///# }
+/// ```
+///
+/// ```compile_fail,E0382
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// fn take<T: std::ops::Add<i32, Output=i32>>(val: T) -> i32 { val + 0 }
+///
+/// let a = erase!(21, std::ops::Add<i32, Output=i32>);
+/// assert_eq!(take(a) + take(a), 42);
+///# }
+/// ```
+///
+/// Lifetime bounds pass through the same way, so `+ 'static` rejects values
+/// which borrow from the stack, and gives an erased value that can go
+/// anywhere a `'static` one is needed.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// fn keep<T: std::fmt::Debug + 'static>(val: T) -> Box<dyn std::fmt::Debug> {
+///     Box::new(val)
+/// }
+///
+/// let a = erase!(String::from("owned"), std::fmt::Debug + 'static);
+/// assert_eq!(format!("{:?}", keep(a)), "\"owned\"");
+///# }
+/// ```
+///
+/// ```compile_fail,E0597
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let s = String::from("borrowed");
+/// let a = erase!(&s, std::fmt::Debug + 'static);
+///# }
+/// ```
+///
+/// The trait can be named by any path, including paths through `crate::`
+/// and fully qualified paths starting with `::`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// mod shapes {
+///     pub trait Area {
+///         fn area(&self) -> u32;
+///     }
+///
+///     impl Area for (u32, u32) {
+///         fn area(&self) -> u32 { self.0 * self.1 }
+///     }
+/// }
+///
+/// use shapes::Area;
+///
+/// fn main() {
+///     let a = erase!((2, 3), crate::shapes::Area);
+///     assert_eq!(a.area(), 6);
+///
+///     let b = erase!(vec![1], ::std::fmt::Debug + ::std::clone::Clone);
+///     assert_eq!(format!("{:?}", b.clone()), "[1]");
+/// }
+/// ```
+///
+/// Traits with generic methods aren't object safe, but they can still be
+/// erased, and their methods called with any type arguments.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use std::fmt::Display;
+///
+/// trait Label {
+///     fn label<X: Display>(&self, x: X) -> String;
+/// }
+///
+/// impl Label for u8 {
+///     fn label<X: Display>(&self, x: X) -> String {
+///         format!("{}: {}", self, x)
+///     }
+/// }
+///
+/// fn main() {
+///     let a = erase!(1u8, Label);
+///     assert_eq!(a.label("one"), "1: one");
+///     assert_eq!(a.label(1.5), "1: 1.5");
+/// }
+/// ```
+///
+/// The same goes for methods taking `impl Trait` arguments, which are
+/// generic methods written another way.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Sink {
+///     fn consume(&self, items: impl IntoIterator<Item = u32>) -> u32;
+/// }
+///
+/// impl Sink for u32 {
+///     fn consume(&self, items: impl IntoIterator<Item = u32>) -> u32 {
+///         items.into_iter().map(|x| x * self).sum()
+///     }
+/// }
+///
+/// fn main() {
+///     let a = erase!(10u32, Sink);
+///     assert_eq!(a.consume(vec![1, 2]), 30);
+///     assert_eq!(a.consume(Some(5)), 50);
+/// }
+/// ```
+///
+/// Methods that take `self` by value and return `Self` return the erased
+/// type, so builder-style chains keep working after erasure.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Builder: Sized {
+///     fn with(self, part: &str) -> Self;
+///     fn build(self) -> String;
+/// }
+///
+/// impl Builder for Vec<String> {
+///     fn with(mut self, part: &str) -> Self {
+///         self.push(part.to_string());
+///         self
+///     }
+///
+///     fn build(self) -> String {
+///         self.join(" ")
+///     }
+/// }
+///
+/// fn main() {
+///     let builder = erase!(Vec::new(), Builder);
+///     assert_eq!(builder.with("hello").with("world").build(), "hello world");
+/// }
+/// ```
+///
+/// Methods of the trait keep their declared return types, so their results
+/// can be used with `?` as usual.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct ParseErr;
+///
+/// trait Field {
+///     fn parse(&self) -> Result<i32, ParseErr>;
+/// }
+///
+/// impl Field for &'static str {
+///     fn parse(&self) -> Result<i32, ParseErr> {
+///         str::parse(self).map_err(|_| ParseErr)
+///     }
+/// }
+///
+/// fn sum(a: &'static str, b: &'static str) -> Result<i32, ParseErr> {
+///     let a = erase!(a, Field);
+///     let b = erase!(b, Field);
+///     Ok(a.parse()? + b.parse()?)
+/// }
+///
+/// fn main() {
+///     assert_eq!(sum("1", "2"), Ok(3));
+///     assert_eq!(sum("1", "x"), Err(ParseErr));
+/// }
+/// ```
+///
+/// Operator traits keep their syntax, including indexing with `Index` once
+/// its `Output` is given.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::ops::Index;
+///
+/// let a = erase!(vec![10, 20, 30], Index<usize, Output = i32>);
+/// assert_eq!(a[1], 20);
+///# }
+/// ```
+///
+/// Traits with generic associated types work the same way, and the methods
+/// returning them can be called on the erased value. The associated type is
+/// only known to satisfy its bounds, so here the iterator can't be reversed.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Bytes {
+///     type Iter<'a>: Iterator<Item = &'a u8> where Self: 'a;
+///
+///     fn bytes<'a>(&'a self) -> Self::Iter<'a>;
+/// }
+///
+/// impl Bytes for Vec<u8> {
+///     type Iter<'a> = std::slice::Iter<'a, u8>;
+///
+///     fn bytes<'a>(&'a self) -> Self::Iter<'a> {
+///         self.iter()
+///     }
+/// }
+///
+/// fn main() {
+///     let a = erase!(vec![1u8, 2, 3], Bytes);
+///     assert_eq!(a.bytes().sum::<u8>(), 6);
+///     assert_eq!(a.bytes().max(), Some(&3));
+/// }
+/// ```
+///
+/// Associated type bounds, available since Rust 1.79, can be used to erase a
+/// value while only requiring something of an associated type.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let items = erase!(vec![1, 2, 3].into_iter(), Iterator<Item: std::fmt::Display>);
+/// let shown: Vec<String> = items.map(|item| item.to_string()).collect();
+/// assert_eq!(shown, ["1", "2", "3"]);
+///# }
+/// ```
+///
+/// Async blocks can be erased into `Future`, and awaited later. An async
+/// block that borrows local variables can be erased too, since the erased
+/// type keeps the borrow.
+///
+/// ```edition2018
+///# #[macro_use]
+///# extern crate whiteout;
+///# use std::future::Future;
+///# use std::sync::Arc;
+///# use std::task::{Context, Poll, Wake};
+///# struct Unpark(std::thread::Thread);
+///# impl Wake for Unpark {
+///#     fn wake(self: Arc<Self>) { self.0.unpark() }
+///# }
+///# fn block_on<F: Future>(fut: F) -> F::Output {
+///#     let mut fut = Box::pin(fut);
+///#     let waker = Arc::new(Unpark(std::thread::current())).into();
+///#     let mut cx = Context::from_waker(&waker);
+///#     loop {
+///#         if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+///#             return out;
+///#         }
+///#         std::thread::park();
+///#     }
+///# }
+///# fn main() {
+/// async fn compute() -> i32 { 20 }
+///
+/// let offset = 1;
+/// let fut = erase!(async { compute().await + offset }, std::future::Future<Output=i32>);
+/// assert_eq!(block_on(fut), 21);
+///# }
+/// ```
+///
+/// Closures can be erased into the `Fn` family of traits, written with their
+/// usual parenthesized syntax.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let add_one = erase!(|x: i32| x + 1, Fn(i32) -> i32);
+/// assert_eq!(add_one(1), 2);
+/// assert_eq!(add_one(2), 3);
+///
+/// let mut total = 0;
+/// {
+///     let mut accumulate = erase!(|x: i32| { total += x; total }, FnMut(i32) -> i32);
+///     assert_eq!(accumulate(1), 1);
+///     assert_eq!(accumulate(2), 3);
+/// }
+/// assert_eq!(total, 3);
+///
+/// let name = String::from("whiteout");
+/// let take = erase!(move || name, FnOnce() -> String);
+/// assert_eq!(take(), "whiteout");
+///# }
+/// ```
+///
+/// Higher-ranked bounds work too, so a closure can be erased into one that
+/// accepts borrows of any lifetime.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let first = erase!(
+///     |s: &str| s.split(' ').next().unwrap_or(""),
+///     for<'a> Fn(&'a str) -> &'a str
+/// );
+///
+/// let long_lived = String::from("hello world");
+/// let a = first(&long_lived);
+/// let b = {
+///     let short_lived = String::from("short lived");
+///     first(&short_lived).len()
+/// };
+/// assert_eq!(a, "hello");
+/// assert_eq!(b, 5);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase {
+    // Several values sharing one eraser
+    (($($val:expr),+ $(,)?) => $($tr:tt)*) => {
+        $crate::tuple_erase!(($($val),+), $($tr)*)
+    };
+    ($val:expr, $($tr:tt)*) => {
+        // Creates a block to operate in
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            // Immediately use this function
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// `eraser_ref!(name, trait)` creates a function with the given identifier
+/// that erases a shared reference to any type implementing the given trait
+/// into a reference to an anonymous type that is `impl Trait`.
+///
+/// The returned reference borrows from the argument, so the usual borrowing
+/// rules still apply to the erased value.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser_ref!(erase_display, std::fmt::Display);
+///
+/// fn main() {
+///     let s = String::from("hello");
+///     let a = erase_display(&s);
+///     assert_eq!(format!("{}", a), "hello");
+/// }
+/// ```
+///
+/// The trait may start with `?Sized +`, so that references to unsized types
+/// such as `str` and `[u8]` can be erased too. The same works for
+/// `erase_ref!`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser_ref!(erase_unsized, ?Sized + std::fmt::Display);
+///
+/// fn main() {
+///     let s = String::from("hello");
+///     let a = erase_unsized(&s[1..]);
+///     let b = erase_unsized("world");
+///     assert_eq!(format!("{} {}", a, b), "ello world");
+///
+///     let c = erase_ref!(&[1u8, 2][..], ?Sized + std::fmt::Debug);
+///     assert_eq!(format!("{:?}", c), "[1, 2]");
+/// }
+/// ```
+///
+/// A reference to an array is not coerced to a slice on its own, because the
+/// eraser is generic over the referenced type. Naming the slice type with a
+/// turbofish brings the coercion back, and gives references to slices and
+/// arrays the same erased type.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Checksum {
+///     fn checksum(&self) -> u32;
+/// }
+///
+/// impl Checksum for [u8] {
+///     fn checksum(&self) -> u32 {
+///         self.iter().map(|&b| b as u32).sum()
+///     }
+/// }
+///
+/// eraser_ref!(erase_checksum, ?Sized + Checksum);
+///
+/// fn main() {
+///     let bytes = vec![1u8, 2, 3];
+///     let array = [4u8; 4];
+///     let erased = [
+///         erase_checksum::<[u8]>(&bytes),
+///         erase_checksum::<[u8]>(&array),
+///     ];
+///     assert_eq!(erased[0].checksum(), 6);
+///     assert_eq!(erased[1].checksum(), 16);
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_ref {
+    ($vis:vis $name:ident, $($tr:tt)*) => {
+            // The returned lifetime is elided to that of the argument
+            $vis fn $name<T: $($tr)*>(val: &T) -> &(impl $($tr)*) {
+                val
+            }
+    }
+}
+
+
+/// `erase_ref!(&value, trait)` turns a shared reference to a value of any type
+/// that implements trait into a reference to an erased type which is
+/// `impl Trait` for that trait.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let s = String::from("hello");
+/// let a = erase_ref!(&s, std::fmt::Display);
+/// assert_eq!(format!("{}", a), "hello");
+/// // The original value is still available
+/// assert_eq!(s, "hello");
+///# }
+/// ```
+///
+/// Borrowed data can be erased to `ToOwned` with its `Owned` type given, so
+/// that `to_owned` gives a value of that type, which can be erased again. For
+/// `str`, this needs `?Sized +` like in `eraser_ref!`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser!(erase_owned, std::fmt::Display + AsRef<str>);
+///
+/// fn main() {
+///     let word = erase_ref!("cow", ?Sized + ToOwned<Owned = String>);
+///     let mut owned = word.to_owned();
+///     owned.push_str("s");
+///     // Both owned values share one erased type
+///     let erased = [erase_owned(owned), erase_owned(word.to_owned())];
+///     assert_eq!(format!("{} {}", erased[0], erased[1]), "cows cow");
+///     assert_eq!(erased[1].as_ref().len(), 3);
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_ref {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser_ref!(__whiteout_erase, $($tr)*);
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// `erase_borrow!(value, trait)` erases a shared borrow of a value rather than
+/// the value itself, producing a reference to an erased type which is
+/// `impl Trait` for that trait. The value is not moved.
+///
+/// This is `erase_ref!(&value, trait)`, and is useful when the value is a
+/// place that can't be moved out of, such as the target of a reference.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// #[derive(Debug)]
+/// struct Big {
+///     data: [u64; 64],
+///     name: String,
+/// }
+///
+/// let big = Big { data: [0; 64], name: "big".to_string() };
+/// let r = &big;
+/// // erase!(*r, std::fmt::Debug) would try to move out of the reference
+/// let a = erase_borrow!(*r, std::fmt::Debug);
+/// assert!(format!("{:?}", a).starts_with("Big"));
+/// assert_eq!(big.name, "big");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_borrow {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erase_ref!(&$val, $($tr)*)
+    }
+}
+
+
+/// `eraser_mut!(name, trait)` creates a function with the given identifier
+/// that erases a mutable reference to any type implementing the given trait
+/// into a mutable reference to an anonymous type that is `impl Trait`.
+///
+/// The returned reference keeps the mutable borrow of the argument alive, so
+/// the original value cannot be aliased while the erased reference is in use.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use std::io::Write;
+///
+/// eraser_mut!(erase_write, std::io::Write);
+///
+/// fn main() {
+///     let mut buf: Vec<u8> = Vec::new();
+///     {
+///         let w = erase_write(&mut buf);
+///         w.write_all(b"hello").unwrap();
+///     }
+///     assert_eq!(buf, b"hello");
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_mut {
+    ($vis:vis $name:ident, $($tr:tt)*) => {
+            // The returned lifetime is elided to that of the argument
+            $vis fn $name<T: $($tr)*>(val: &mut T) -> &mut (impl $($tr)*) {
+                val
+            }
+    }
+}
+
+
+/// `erase_mut!(&mut value, trait)` turns a mutable reference to a value of any
+/// type that implements trait into a mutable reference to an erased type which
+/// is `impl Trait` for that trait.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::io::Write;
+///
+/// let mut buf: Vec<u8> = Vec::new();
+/// {
+///     let w = erase_mut!(&mut buf, std::io::Write);
+///     w.write_all(&[1, 2, 3]).unwrap();
+/// }
+/// assert_eq!(buf, [1, 2, 3]);
+///# }
+/// ```
+///
+/// The reference is reborrowed rather than moved, so the same `&mut` can be
+/// erased again once the previous erased reference is no longer used.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::io::Write;
+///
+/// let mut buf: Vec<u8> = Vec::new();
+/// let r = &mut buf;
+/// erase_mut!(r, std::io::Write).write_all(b"ab").unwrap();
+/// erase_mut!(r, std::io::Write).write_all(b"cd").unwrap();
+/// r.push(b'e');
+/// assert_eq!(buf, b"abcde");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_mut {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser_mut!(__whiteout_erase, $($tr)*);
+            __whiteout_erase(&mut *$val)
+        }
+    }
+}
+
+
+/// `erase_fmt_write!(&mut sink)` erases a mutable reference to any
+/// `std::fmt::Write` sink, such as a `String`, into a mutable reference to an
+/// anonymous type that is `impl std::fmt::Write`.
+///
+/// This is `erase_mut!(sink, std::fmt::Write)`, but it also works without
+/// `std`, so a formatting routine can write to whatever sink it's given.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::fmt::Write;
+///
+/// let mut s = String::new();
+/// {
+///     let w = erase_fmt_write!(&mut s);
+///     write!(w, "{}-{}", 1, 2).unwrap();
+/// }
+/// assert_eq!(s, "1-2");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_fmt_write {
+    ($sink:expr) => {
+        $crate::erase_mut!($sink, $crate::__private::FmtWrite)
+    }
+}
+
+
+/// `boxerase!(value, trait)` boxes a value of any type that implements trait
+/// and coerces it into a `Box<dyn Trait>`.
+///
+/// Unlike `erase!`, the result has a nameable type, so values erased this way
+/// can be stored together. As with any `Box<dyn Trait>`, the trait object is
+/// `'static`.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Describe {
+///     fn describe(&self) -> String;
+/// }
+///
+/// impl Describe for i32 {
+///     fn describe(&self) -> String { format!("int {}", self) }
+/// }
+///
+/// impl Describe for f64 {
+///     fn describe(&self) -> String { format!("float {}", self) }
+/// }
+///
+/// impl Describe for &'static str {
+///     fn describe(&self) -> String { format!("str {}", self) }
+/// }
+///
+/// fn main() {
+///     let values: Vec<Box<dyn Describe>> = vec![
+///         boxerase!(10, Describe),
+///         boxerase!(2.5, Describe),
+///         boxerase!("hi", Describe),
+///     ];
+///     let described: Vec<String> = values.iter().map(|v| v.describe()).collect();
+///     assert_eq!(described, ["int 10", "float 2.5", "str hi"]);
+///
+///     // Fully qualified paths work too
+///     let c = boxerase!(1, ::std::fmt::Debug);
+///     assert_eq!(format!("{:?}", c), "1");
+/// }
+/// ```
+///
+/// A lifetime may be added to the trait, giving a `Box<dyn Trait + 'a>` that
+/// can hold borrowed data rather than requiring `'static` values.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// use std::fmt::Display;
+///
+/// fn shout<'a>(word: &'a str) -> Box<dyn Display + 'a> {
+///     boxerase!(word, Display + 'a)
+/// }
+///
+///# fn main() {
+/// let word = String::from("hey");
+/// {
+///     let boxed = shout(&word);
+///     assert_eq!(boxed.to_string(), "hey");
+///     // The box is dropped here, while `word` is still borrowed
+/// }
+/// assert_eq!(word, "hey");
+///# }
+/// ```
+///
+/// Trait objects are only `Send` or `Sync` if they say so, unlike erased
+/// types, which leak them. Adding `+ Send + Sync` to the trait gives a
+/// `Box<dyn Trait + Send + Sync>`, which can cross threads.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::fmt::Display;
+/// use std::sync::mpsc;
+/// use std::thread;
+///
+/// let (tx, rx) = mpsc::channel::<Box<dyn Display + Send + Sync>>();
+/// let sender = thread::spawn(move || {
+///     tx.send(boxerase!(1, Display + Send + Sync)).unwrap();
+///     tx.send(boxerase!("two", Display + Send + Sync)).unwrap();
+/// });
+/// sender.join().unwrap();
+/// let received: Vec<String> = rx.iter().map(|v| v.to_string()).collect();
+/// assert_eq!(received, ["1", "two"]);
+///# }
+/// ```
+///
+/// Values that aren't `Send` and `Sync` are rejected:
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = boxerase!(std::rc::Rc::new(1), std::fmt::Display + Send + Sync);
+///# }
+/// ```
+///
+/// The trait must be object safe. A trait that isn't, such as one with a
+/// generic method, is rejected with an error pointing at the trait in the
+/// invocation and naming the method that is the cause; `erase!` can be used
+/// for such traits instead.
+///
+/// ```compile_fail,E0038
+///# #[macro_use]
+///# extern crate whiteout;
+/// trait Label {
+///     fn label<X: std::fmt::Display>(&self, x: X) -> String;
+/// }
+///
+/// impl Label for u8 {
+///     fn label<X: std::fmt::Display>(&self, x: X) -> String {
+///         format!("{}: {}", self, x)
+///     }
+/// }
+///
+///# fn main() {
+/// let a = boxerase!(1u8, Label);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! boxerase {
+    ($val:expr, $($tr:tt)*) => {
+        // Box once and let the annotation coerce to the trait object
+        {
+            let boxed: $crate::__private::Box<$crate::__dyn!($($tr)*)> = $crate::__private::Box::new($val);
+            boxed
+        }
+    }
+}
+
+// The trait object type for a trait. `dyn ::path` would be read as a path
+// starting with `dyn`, so a first bound starting with `::` is put in
+// parentheses, which leaves the path as it was written.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dyn {
+    (@first [$($first:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::__dyn!(@first [$($first)* <] [< $($depth)*] $($rest)*)
+    };
+    (@first [$($first:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::__dyn!(@first [$($first)* >] [$($depth)*] $($rest)*)
+    };
+    (@first [$($first:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::__dyn!(@first [$($first)* >>] [$($depth)*] $($rest)*)
+    };
+    // A `+` outside any angle brackets ends the first bound
+    (@first [$($first:tt)*] [] + $($rest:tt)*) => {
+        dyn ($($first)*) + $($rest)*
+    };
+    (@first [$($first:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__dyn!(@first [$($first)* $next] [$($depth)*] $($rest)*)
+    };
+    (@first [$($first:tt)*] []) => {
+        dyn ($($first)*)
+    };
+    (:: $($tr:tt)*) => {
+        $crate::__dyn!(@first [::] [] $($tr)*)
+    };
+    ($($tr:tt)*) => {
+        dyn $($tr)*
+    };
+}
+
+
+/// `erase_boxed_fn!(closure, Fn(args) -> ret)` boxes a closure as a
+/// `Box<dyn Fn(args) -> ret>`, and likewise for `FnMut` and `FnOnce`.
+///
+/// Unlike `erase!`, which gives each closure its own anonymous type, the
+/// boxed closures all have one nameable type, so closures with different
+/// captures can be stored together.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let offset = 10;
+/// let factor = 3;
+/// let ops: Vec<Box<dyn Fn(i32) -> i32>> = vec![
+///     erase_boxed_fn!(move |x| x + offset, Fn(i32) -> i32),
+///     erase_boxed_fn!(move |x| x * factor, Fn(i32) -> i32),
+/// ];
+/// let results: Vec<i32> = ops.iter().map(|op| op(2)).collect();
+/// assert_eq!(results, [12, 6]);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! erase_boxed_fn {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::boxerase!($val, $($tr)*)
+    }
+}
+
+
+/// `push_erased!(registry, value, trait)` boxes a value as a
+/// `Box<dyn Trait>`, like `boxerase!`, and pushes it onto an existing
+/// `Vec<Box<dyn Trait>>`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// trait Plugin {
+///     fn name(&self) -> String;
+/// }
+///
+/// struct Logger;
+/// struct Version(u32);
+///
+/// impl Plugin for Logger {
+///     fn name(&self) -> String {
+///         "logger".to_string()
+///     }
+/// }
+///
+/// impl Plugin for Version {
+///     fn name(&self) -> String {
+///         format!("v{}", self.0)
+///     }
+/// }
+///
+/// impl Plugin for &'static str {
+///     fn name(&self) -> String {
+///         self.to_string()
+///     }
+/// }
+///
+///# fn main() {
+/// let mut registry: Vec<Box<dyn Plugin>> = Vec::new();
+/// push_erased!(registry, Logger, Plugin);
+/// push_erased!(registry, Version(2), Plugin);
+/// push_erased!(registry, "custom", Plugin);
+/// let names: Vec<String> = registry.iter().map(|p| p.name()).collect();
+/// assert_eq!(names, ["logger", "v2", "custom"]);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! push_erased {
+    ($registry:expr, $val:expr, $($tr:tt)*) => {
+        $registry.push($crate::boxerase!($val, $($tr)*))
+    }
+}
+
+
+/// `upcast_erase!(boxed, trait)` turns a `Box<dyn Sub>` into a `Box<dyn Trait>`
+/// for a supertrait of `Sub`, without boxing the value again.
+///
+/// This lets values be boxed as a rich trait and later narrowed to a smaller
+/// one. Upcasting trait objects needs Rust 1.86 or later.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// trait Polygon: Shape {
+///     fn sides(&self) -> u32;
+/// }
+///
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// impl Polygon for Square {
+///     fn sides(&self) -> u32 {
+///         4
+///     }
+/// }
+///
+///# fn main() {
+/// let polygon = boxerase!(Square(2.0), Polygon);
+/// assert_eq!(polygon.sides(), 4);
+/// let shape = upcast_erase!(polygon, Shape);
+/// assert_eq!(shape.area(), 4.0);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! upcast_erase {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            let upcast: $crate::__private::Box<$crate::__dyn!($($tr)*)> = $val;
+            upcast
+        }
+    }
+}
+
+
+/// `erase_iter!(iterator, item)` turns an iterator of any type whose items are
+/// of the given type into an erased type which is
+/// `impl Iterator<Item = item>`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let evens = (1u32..10).filter(|x| x % 2 == 0).map(|x| x * 10);
+/// let a = erase_iter!(evens, u32);
+/// assert_eq!(a.collect::<Vec<u32>>(), [20, 40, 60, 80]);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_iter {
+    ($val:expr, $item:ty) => {
+        $crate::erase!($val, Iterator<Item = $item>)
+    }
+}
+
+
+/// `erase_from_fn!(closure, item)` turns a closure returning `Option<item>`
+/// into an erased type which is `impl Iterator<Item = item>`, like
+/// `erase_iter!(std::iter::from_fn(closure), item)`.
+///
+/// Iterators made this way have the closure's type in theirs, so they can't
+/// be named without erasure.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let mut count = 0;
+/// let counter = erase_from_fn!(move || {
+///     count += 1;
+///     Some(count)
+/// }, u32);
+/// assert_eq!(counter.take(3).collect::<Vec<u32>>(), [1, 2, 3]);
+///
+/// // erase_iter! works with such iterators too
+/// let mut left = 2;
+/// let countdown = erase_iter!(std::iter::from_fn(move || {
+///     left -= 1;
+///     if left >= 0 { Some(left) } else { None }
+/// }), i32);
+/// assert_eq!(countdown.collect::<Vec<i32>>(), [1, 0]);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_from_fn {
+    ($val:expr, $item:ty) => {
+        $crate::erase_iter!($crate::__private::iter_from_fn($val), $item)
+    }
+}
+
+
+/// `erase_into_iter!(collection, item)` turns anything that can be iterated
+/// over with items of the given type into an erased type which is
+/// `impl IntoIterator<Item = item>`, so it can still be used in a `for` loop.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let bytes = erase_into_iter!(vec![1u8, 2, 3], u8);
+/// let mut sum = 0;
+/// for b in bytes {
+///     sum += b;
+/// }
+/// assert_eq!(sum, 6);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_into_iter {
+    ($val:expr, $item:ty) => {
+        $crate::erase!($val, IntoIterator<Item = $item>)
+    }
+}
+
+
+/// `erase_iter_boxed!(iterator, item)` boxes an iterator of any type whose
+/// items are of the given type into a `Box<dyn Iterator<Item = item>>`.
+///
+/// Unlike `erase_iter!`, iterators built in different ways come out with the
+/// same type, so they can be stored together.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let mut pipelines = vec![
+///     erase_iter_boxed!((1..4).map(|x| x * 2), i32),
+///     erase_iter_boxed!(vec![7, 8].into_iter().rev(), i32),
+/// ];
+/// let drained: Vec<Vec<i32>> = pipelines.iter_mut().map(|p| p.collect()).collect();
+/// assert_eq!(drained, [vec![2, 4, 6], vec![8, 7]]);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! erase_iter_boxed {
+    ($val:expr, $item:ty) => {
+        $crate::boxerase!($val, Iterator<Item = $item>)
+    }
+}
+
+
+/// `erase_dyn_iter!(iterable, trait)` boxes every item of anything that can
+/// be iterated over as a trait object, producing an erased type which is
+/// `impl Iterator<Item = Box<dyn Trait>>`.
+///
+/// The items are boxed lazily, as the iterator is consumed, so a stream of
+/// trait objects can be produced without collecting it first. The items must
+/// be `'static`, like the trait objects they become.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let mut items = erase_dyn_iter!((1..).map(|x| x * x), std::fmt::Debug);
+/// let first: Vec<String> = items.by_ref().take(3).map(|item| format!("{:?}", item)).collect();
+/// assert_eq!(first, ["1", "4", "9"]);
+/// assert_eq!(format!("{:?}", items.next().unwrap()), "16");
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! erase_dyn_iter {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erase_iter!(
+            $crate::__private::IntoIterator::into_iter($val).map(|item| $crate::boxerase!(item, $($tr)*)),
+            $crate::__private::Box<$crate::__dyn!($($tr)*)>
+        )
+    };
+}
+
+
+/// `erase_all!([values...], trait)` erases every value in the list through a
+/// single eraser function, producing an array whose elements all share one
+/// anonymous type that is `impl Trait` for the given trait.
+///
+/// Since the eraser is shared, the values must all be of the same type.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let [a, b, c] = erase_all!([1, 2, 3], MyTrait);
+/// // These can be used together, unlike separate calls to erase!
+/// assert_eq!(a + b + c, 6.into());
+///# }
+/// ```
+///
+/// With `Ord` in the trait, the erased values can be sorted, and sort in the
+/// same order as the values they came from.
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let mut values = Vec::from(erase_all!([3, 1, 2], MyTrait + Ord));
+/// values.sort();
+/// assert_eq!(values, [1.into(), 2.into(), 3.into()]);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_all {
+    ([$($val:expr),* $(,)?], $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            [$(__whiteout_erase($val)),*]
+        }
+    }
+}
+
+
+/// `unerase!(erased, type)` attempts to recover a reference to the concrete
+/// type of an erased value, returning `Some(&type)` if the erased value is of
+/// that type and `None` otherwise.
+///
+/// This only works when the erasing trait is bounded by `core::any::Any`,
+/// since the check is done with `Any::downcast_ref`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(10i32, std::any::Any + std::fmt::Debug);
+/// assert_eq!(unerase!(a, i32), Some(&10));
+/// assert_eq!(unerase!(a, String), None);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! unerase {
+    ($val:expr, $t:ty) => {
+        // The parentheses keep `dyn` from being read as a path segment
+        (&$val as &dyn ($crate::__private::Any)).downcast_ref::<$t>()
+    }
+}
+
+
+/// `const_eraser!(name, trait)` is like `eraser!`, but creates a `const fn`,
+/// so that the eraser can be used in const contexts.
+///
+/// The value passed in must be const-compatible; in particular, its type
+/// must not need to be dropped at compile time. Since the erased type can't
+/// be named, it can't be the declared type of a `const` or `static` item, but
+/// it can be produced in `const` blocks and returned from other `const fn`s.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// const_eraser!(erase_copy, Copy + std::fmt::Debug);
+///
+/// const fn table() -> impl Copy + std::fmt::Debug {
+///     erase_copy([1, 2, 3])
+/// }
+///
+/// fn main() {
+///     // Evaluated at compile time
+///     let a = const { erase_copy(10) };
+///     let t = const { table() };
+///     assert_eq!(format!("{:?} {:?}", a, t), "10 [1, 2, 3]");
+/// }
+/// ```
+///
+/// The eraser can be called from `const fn`s which are generic themselves,
+/// with the trait as a bound on their parameters.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Unit: Copy + std::fmt::Debug {}
+/// impl Unit for u8 {}
+/// impl Unit for char {}
+///
+/// const_eraser!(erase_unit, Unit);
+///
+/// const fn pick<T: Unit>(first: T, second: T, take_first: bool) -> impl Unit {
+///     erase_unit(if take_first { first } else { second })
+/// }
+///
+/// fn main() {
+///     let a = const { pick(1u8, 2u8, false) };
+///     let b = const { pick('x', 'y', true) };
+///     assert_eq!(format!("{:?} {:?}", a, b), "2 'x'");
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! const_eraser {
+    ($(#[$attr:meta])* $vis:vis $name:ident $($rest:tt)*) => {
+        $crate::eraser!(@fn [const] [$(#[$attr])* $vis] $name $($rest)*);
+    }
+}
+
+
+/// `const_erase!(value, trait)` is like `erase!`, but can be used in const
+/// contexts. See `const_eraser!` for the restrictions that apply.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = const { const_erase!(10, Copy + std::fmt::Debug) };
+/// assert_eq!(format!("{:?}", a), "10");
+///# }
+/// ```
+///
+/// Plain `erase!` can't be used in const contexts, since its eraser is not
+/// a `const fn`.
+///
+/// ```compile_fail,E0015
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = const { erase!(10, Copy + std::fmt::Debug) };
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! const_erase {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::const_eraser!(__whiteout_erase, $($tr)*);
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// `tuple_erase!((values...), trait)` erases every element of a tuple through
+/// a single eraser function, producing a tuple of the same arity whose
+/// elements are `impl Trait` for the given trait.
+///
+/// As with `erase_all!`, the erased elements only share one anonymous type if
+/// the values share one concrete type, since the eraser is generic. Values of
+/// different types can be converted to a common type first.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let (a, b, c) = tuple_erase!((i64::from(1u8), i64::from(2u16), i64::from(3u32)), MyTrait);
+/// assert_eq!(a + b + c, 6.into());
+///
+/// let (single,) = tuple_erase!((10,), std::fmt::Debug);
+/// assert_eq!(format!("{:?}", single), "10");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! tuple_erase {
+    (($($val:expr),+ $(,)?), $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            ($(__whiteout_erase($val),)+)
+        }
+    }
+}
+
+
+/// `rc_erase!(value, trait)` puts a value of any type that implements trait
+/// into an `Rc` and coerces it into an `Rc<dyn Trait>`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::rc::Rc;
+///
+/// let a = rc_erase!(10, std::fmt::Display);
+/// let b = Rc::clone(&a);
+/// assert_eq!(format!("{} {}", a, b), "10 10");
+/// assert_eq!(Rc::strong_count(&a), 2);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! rc_erase {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            let rc: $crate::__private::Rc<$crate::__dyn!($($tr)*)> = $crate::__private::Rc::new($val);
+            rc
+        }
+    }
+}
+
+
+/// `arc_erase!(value, trait)` puts a value of any type that implements trait
+/// into an `Arc` and coerces it into an `Arc<dyn Trait>`.
+///
+/// The result is only `Send` and `Sync` if those are requested as part of the
+/// trait, as in `arc_erase!(value, Trait + Send + Sync)`; otherwise they are
+/// not required of the value.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let a = arc_erase!(10, std::fmt::Display);
+/// let b = Arc::clone(&a);
+/// assert_eq!(format!("{} {}", a, b), "10 10");
+///
+/// let shared = arc_erase!(20, std::fmt::Display + Send + Sync);
+/// let handle = {
+///     let shared = Arc::clone(&shared);
+///     thread::spawn(move || format!("{}", shared))
+/// };
+/// assert_eq!(handle.join().unwrap(), "20");
+/// assert_eq!(format!("{}", shared), "20");
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! arc_erase {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            let arc: $crate::__private::Arc<$crate::__dyn!($($tr)*)> = $crate::__private::Arc::new($val);
+            arc
+        }
+    }
+}
+
+
+/// `weak_erase!(Rc, value, trait)` puts a value of any type that implements
+/// trait into an `Rc<dyn Trait>` like `rc_erase!`, and returns it along with
+/// a `Weak<dyn Trait>` to the same value. `weak_erase!(Arc, value, trait)`
+/// does the same with `Arc` and `sync::Weak`.
+///
+/// The strong handle owns the value; the weak one doesn't keep it alive, so
+/// it can only be upgraded while some strong handle remains. To get a weak
+/// handle from an existing `Rc<dyn Trait>`, use `Rc::downgrade`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let (strong, weak) = weak_erase!(Rc, 10, std::fmt::Display);
+/// assert_eq!(format!("{}", weak.upgrade().unwrap()), "10");
+/// drop(strong);
+/// assert!(weak.upgrade().is_none());
+///
+/// let (strong, weak) = weak_erase!(Arc, 20, std::fmt::Display + Send + Sync);
+/// let handle = std::thread::spawn(move || weak.upgrade().map(|v| v.to_string()));
+/// assert_eq!(handle.join().unwrap().as_deref(), Some("20"));
+/// drop(strong);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! weak_erase {
+    (Rc, $val:expr, $($tr:tt)*) => {
+        {
+            let strong = $crate::rc_erase!($val, $($tr)*);
+            let weak = $crate::__private::Rc::downgrade(&strong);
+            (strong, weak)
+        }
+    };
+    (Arc, $val:expr, $($tr:tt)*) => {
+        {
+            let strong = $crate::arc_erase!($val, $($tr)*);
+            let weak = $crate::__private::Arc::downgrade(&strong);
+            (strong, weak)
+        }
+    };
+}
+
+
+/// `refcell_erase!(value, trait)` boxes a value of any type that implements
+/// trait as a trait object inside a `RefCell`, producing a
+/// `RefCell<Box<dyn Trait>>`. `refcell_erase!(Rc, value, trait)` produces an
+/// `Rc<RefCell<dyn Trait>>` instead, which can be shared, as between the
+/// nodes of a graph.
+///
+/// Either way, the `&mut self` methods of the trait can be called through
+/// `borrow_mut` while the value is shared.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// trait Counter {
+///     fn bump(&mut self) -> u32;
+/// }
+///
+/// impl Counter for u32 {
+///     fn bump(&mut self) -> u32 {
+///         *self += 1;
+///         *self
+///     }
+/// }
+///
+///# fn main() {
+/// use std::rc::Rc;
+///
+/// let a = refcell_erase!(0u32, Counter);
+/// a.borrow_mut().bump();
+/// assert_eq!(a.borrow_mut().bump(), 2);
+///
+/// let shared = refcell_erase!(Rc, 10u32, Counter);
+/// let other = Rc::clone(&shared);
+/// other.borrow_mut().bump();
+/// assert_eq!(shared.borrow_mut().bump(), 12);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! refcell_erase {
+    (Rc, $val:expr, $($tr:tt)*) => {
+        {
+            let rc: $crate::__private::Rc<$crate::__private::RefCell<$crate::__dyn!($($tr)*)>> =
+                $crate::__private::Rc::new($crate::__private::RefCell::new($val));
+            rc
+        }
+    };
+    ($val:expr, $($tr:tt)*) => {
+        $crate::__private::RefCell::new($crate::boxerase!($val, $($tr)*))
+    };
+}
+
+
+/// `erase_named!(Name, value, trait)` erases value to trait and wraps it in
+/// `Name`, a newtype declared beforehand with
+/// `erase_named!(struct Name, trait)`, so that erased values can be stored in
+/// struct fields or returned from trait methods.
+///
+/// The declaration defines `struct Name<T>(T)` at item level, so that it can
+/// be named elsewhere; attributes and a visibility may be given before
+/// `struct`. The newtype derefs to the value it holds, which delegates the
+/// trait's methods to it, and the value has been erased, so those are the
+/// only methods it has. The erased type itself still can't be named, so a
+/// field holding one is generic over it, or is `Name<impl Trait>` where that
+/// is allowed; nothing is boxed, and the trait needn't be object safe.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// trait Describe {
+///     fn describe(&self) -> String;
+/// }
+///
+/// impl Describe for i32 {
+///     fn describe(&self) -> String { format!("int {}", self) }
+/// }
+///
+/// erase_named!(struct Described, Describe);
+///
+/// struct Holder<T> {
+///     value: Described<T>,
+/// }
+///
+/// fn make() -> Described<impl Describe> {
+///     erase_named!(Described, 10, Describe)
+/// }
+///
+/// fn main() {
+///     let holder = Holder { value: make() };
+///     assert_eq!(holder.value.describe(), "int 10");
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_named {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident, $($tr:tt)*) => {
+        $(#[$attr])*
+        $vis struct $name<T>(T);
+
+        impl<T> $name<T> {
+            /// Wraps a value, which should already have been erased.
+            $vis fn new(val: T) -> Self where T: $($tr)* {
+                $name(val)
+            }
+        }
+
+        impl<T> $crate::__private::Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T> $crate::__private::DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut T {
+                &mut self.0
+            }
+        }
+    };
+    ($name:ident, $val:expr, $($tr:tt)*) => {
+        $name::new($crate::erase!($val, $($tr)*))
+    };
+}
+
+
+/// `erase_result!(result, trait)` erases both the `Ok` and the `Err` value of
+/// a `Result` whose types both implement trait, producing a
+/// `Result<impl Trait, impl Trait>`.
+///
+/// Each side goes through its own eraser, so the `Ok` and `Err` types are
+/// distinct anonymous types.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// fn show(r: Result<i32, String>) -> String {
+///     match erase_result!(r, std::fmt::Debug) {
+///         Ok(v) => format!("ok {:?}", v),
+///         Err(e) => format!("err {:?}", e),
+///     }
+/// }
+/// assert_eq!(show(Ok(1)), "ok 1");
+/// assert_eq!(show(Err("bad".to_string())), "err \"bad\"");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_result {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_ok, $($tr)*);
+            $crate::eraser!(__whiteout_err, $($tr)*);
+            $val.map(__whiteout_ok).map_err(__whiteout_err)
+        }
+    }
+}
+
+
+/// `erase_ok!(result, trait)` erases only the `Ok` value of a `Result`,
+/// producing a `Result<impl Trait, E>` whose error type is left as it was.
+///
+/// For the other side, `erase_err!` boxes the error instead, since results
+/// with different error types can only be unified through a trait object.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// fn half(n: i32) -> Result<i32, String> {
+///     if n % 2 == 0 { Ok(n / 2) } else { Err(format!("{} is odd", n)) }
+/// }
+///
+/// let ok = erase_ok!(half(4), std::fmt::Display);
+/// assert_eq!(ok.unwrap().to_string(), "2");
+///
+/// // The error is still a `String`
+/// let err = erase_ok!(half(3), std::fmt::Display);
+/// let message: String = err.err().unwrap();
+/// assert_eq!(message, "3 is odd");
+///# }
+/// ```
+///
+/// The `Ok` value only has the methods of the trait:
+///
+/// ```compile_fail,E0599
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let ok = erase_ok!(Ok::<i32, ()>(2), std::fmt::Display);
+/// let _ = ok.unwrap().pow(2);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_ok {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_ok, $($tr)*);
+            $val.map(__whiteout_ok)
+        }
+    }
+}
+
+
+/// `erase_opt!(option, trait)` erases the value inside an `Option`, producing
+/// an `Option<impl Trait>`.
+///
+/// `None` has the type of whichever `Option` it is used as, so once the
+/// option is erased, `None` can be assigned to it or returned in its place
+/// without naming the erased type.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let mut a = erase_opt!(Some(5), MyTrait);
+/// match a {
+///     Some(ref v) => assert_eq!(*v, 5.into()),
+///     None => unreachable!(),
+/// }
+///
+/// // `None` of the same erased type
+/// a = None;
+/// assert!(a.is_none());
+/// let b = if a.is_none() { erase_opt!(Some(1), MyTrait) } else { None };
+/// assert_eq!(b, Some(1.into()));
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_opt {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            $val.map(__whiteout_erase)
+        }
+    }
+}
+
+
+/// `erase_with_const!(value, NAME: type, trait)` erases a value like `erase!`,
+/// and also returns the value of the trait's associated constant `NAME`.
+///
+/// Associated constants can't be reached through an erased value, since its
+/// type can't be named to write `Type::NAME`. This reads the constant while
+/// the concrete type is still known. The constant's type must be given, and
+/// can't be `Self`, since that would reveal the concrete type.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// trait Bounded: std::fmt::Debug {
+///     const BITS: u32;
+/// }
+///
+/// impl Bounded for u8 {
+///     const BITS: u32 = 8;
+/// }
+///
+/// impl Bounded for u64 {
+///     const BITS: u32 = 64;
+/// }
+///
+///# fn main() {
+/// let (a, a_bits) = erase_with_const!(1u8, BITS: u32, Bounded);
+/// let (b, b_bits) = erase_with_const!(2u64, BITS: u32, Bounded);
+/// assert_eq!(format!("{:?} {:?}", a, b), "1 2");
+/// assert_eq!((a_bits, b_bits), (8, 64));
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_with_const {
+    ($val:expr, $konst:ident : $kty:ty, $($tr:tt)*) => {
+        {
+            fn __whiteout_erase<T: $($tr)*>(val: T) -> (impl $($tr)*, $kty) {
+                (val, T::$konst)
+            }
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// `pin_erase!(value, trait)` pins a value of any type that implements trait
+/// on the heap and coerces it into a `Pin<Box<dyn Trait>>`.
+///
+/// This differs from `boxerase!` in that the value is pinned as soon as it is
+/// boxed, so methods taking `self: Pin<&mut Self>`, like `Future::poll`, can
+/// be called on it.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use std::pin::Pin;
+///
+/// trait Step {
+///     fn step(self: Pin<&mut Self>) -> u32;
+/// }
+///
+/// struct Counter(u32);
+///
+/// impl Step for Counter {
+///     fn step(mut self: Pin<&mut Self>) -> u32 {
+///         self.0 += 1;
+///         self.0
+///     }
+/// }
+///
+/// fn main() {
+///     let mut a: Pin<Box<dyn Step>> = pin_erase!(Counter(0), Step);
+///     assert_eq!(a.as_mut().step(), 1);
+///     assert_eq!(a.as_mut().step(), 2);
+/// }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! pin_erase {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            let pinned: $crate::__private::Pin<$crate::__private::Box<$crate::__dyn!($($tr)*)>> =
+                $crate::__private::Box::pin($val);
+            pinned
+        }
+    }
+}
+
+
+/// `pin_mut_erase!(pinned, trait)` turns a `Pin<&mut T>` for any type that
+/// implements trait into a `Pin<&mut impl Trait>`.
+///
+/// Unlike `pin_erase!`, this doesn't allocate: the value stays where it was
+/// pinned, such as on the stack with `std::pin::pin!`. Only the type of the
+/// reference changes, so no pin projection is involved.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// use std::pin::{pin, Pin};
+///
+/// trait Step {
+///     fn step(self: Pin<&mut Self>) -> u32;
+/// }
+///
+/// struct Counter(u32);
+///
+/// impl Step for Counter {
+///     fn step(mut self: Pin<&mut Self>) -> u32 {
+///         self.0 += 1;
+///         self.0
+///     }
+/// }
+///
+///# fn main() {
+/// let pinned = pin!(Counter(0));
+/// let mut a = pin_mut_erase!(pinned, Step);
+/// assert_eq!(a.as_mut().step(), 1);
+/// assert_eq!(a.as_mut().step(), 2);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! pin_mut_erase {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            fn __whiteout_erase<T: $($tr)*>(val: $crate::__private::Pin<&mut T>)
+                -> $crate::__private::Pin<&mut impl $($tr)*>
+            {
+                val
+            }
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// `define_eraser_trait!(name: bounds)` defines a trait with the given bounds
+/// as supertraits, and implements it for every type that satisfies them, so
+/// that it can be used as the trait for the other macros.
+///
+/// An eraser function for the new trait can be created at the same time by
+/// following the bounds with `;` and the arguments that `eraser!` takes
+/// before its trait, as in `define_eraser_trait!(MyTrait: Debug; pub erase)`.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// // This replaces the hand-written trait and blanket impl from the
+/// // crate-level documentation.
+/// define_eraser_trait!(MyTrait:
+///     std::ops::Add<Self, Output=Self>
+///     + std::convert::From<i32>
+///     + std::fmt::Debug
+///     + PartialEq;
+///     erase_my_trait
+/// );
+///
+/// fn main() {
+///     let a = erase_my_trait(10);
+///     let b = erase_my_trait(5);
+///     assert_eq!(a + b, 15.into());
+///
+///     // The trait can be used with the other macros too
+///     let c = erase!(1, MyTrait);
+///     assert_eq!(c, 1.into());
+/// }
+/// ```
+///
+/// The trait may have type parameters, which the bounds can use. An eraser
+/// for it then declares them as well, as it would for `eraser!`.
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// // Generates trait MyConv<U>: Into<U> + Copy {} and its blanket impl
+/// define_eraser_trait!(MyConv<U>: Into<U> + Copy; erase_conv<U>);
+///
+/// fn main() {
+///     let a = erase_conv(7i32);
+///     let wide: i64 = a.into();
+///     assert_eq!(wide, 7);
+///
+///     let b = erase!(3u8, MyConv<u32>);
+///     assert_eq!(b.into() + 1u32, 4);
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! define_eraser_trait {
+    // Found the eraser; everything before it is the bounds
+    (@split [$($head:tt)*] [$($bounds:tt)*] ; $($eraser:tt)+) => {
+        $crate::define_eraser_trait!(@emit [$($head)*] [$($bounds)*]);
+        $crate::define_eraser_trait!(@eraser [$($head)*] $($eraser)+);
+    };
+    // Move one token of the bounds into the accumulator
+    (@split [$($head:tt)*] [$($bounds:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::define_eraser_trait!(@split [$($head)*] [$($bounds)* $next] $($rest)*);
+    };
+    // Ran out of tokens without finding an eraser
+    (@split [$($head:tt)*] [$($bounds:tt)*]) => {
+        $crate::define_eraser_trait!(@emit [$($head)*] [$($bounds)*]);
+    };
+    (@emit [[$($pre:tt)*] [$($tr:tt)*] [$($gen:tt)*]] [$($bounds:tt)*]) => {
+        $($pre)* trait $($tr)*: $($bounds)* {}
+
+        impl<T $($gen)*> $($tr)* for T where T: $($bounds)* {}
+    };
+    (@eraser [[$($pre:tt)*] [$($tr:tt)*] [$($gen:tt)*]] $($eraser:tt)+) => {
+        $crate::eraser!($($eraser)+, $($tr)*);
+    };
+    // The head is the attributes and visibility, the trait as it is named,
+    // and the extra generics of the blanket impl
+    ($(#[$attr:meta])* $vis:vis $name:ident < $($param:ident),+ $(,)? > : $($rest:tt)+) => {
+        $crate::define_eraser_trait!(@split [[$(#[$attr])* $vis] [$name<$($param),+>] [$(, $param)+]] [] $($rest)+);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident : $($rest:tt)+) => {
+        $crate::define_eraser_trait!(@split [[$(#[$attr])* $vis] [$name] []] [] $($rest)+);
+    };
+}
+
+
+/// `chainable_eraser!(name, bounds)` creates an eraser for bounds that may
+/// mention `Self`, such as `Add<Output = Self>`, so that operations on erased
+/// values give erased values of the same type and can be chained.
+///
+/// `Self` can't be used in the `impl Trait` made by `eraser!`, so this
+/// defines a trait with the bounds as supertraits, like
+/// `define_eraser_trait!`. The trait has the same name as the eraser, which
+/// is allowed since traits and functions live in different namespaces, and
+/// the same visibility.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// chainable_eraser!(erase_num, std::ops::Add<Output = Self> + std::fmt::Debug + PartialEq);
+///
+/// fn main() {
+///     let a = erase_num(1);
+///     let b = erase_num(2);
+///     let c = erase_num(3);
+///     assert_eq!((a + b) + c, erase_num(6));
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! chainable_eraser {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $($tr:tt)*) => {
+        $crate::define_eraser_trait!(
+            #[allow(non_camel_case_types)]
+            // Traits such as `Add` need `Self` to be sized to use it as an operand
+            $vis $name: Sized + $($tr)*;
+            $(#[$attr])* $vis $name
+        );
+    }
+}
+
+
+/// `erase_array!(array, trait)` erases every element of an array of any type
+/// that implements trait, producing an array of the same length whose
+/// elements share one anonymous type that is `impl Trait`.
+///
+/// Unlike collecting into a `Vec`, this keeps the values on the stack and
+/// keeps the length in the type.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let numbers = [1, 2, 3, 4];
+/// let erased = erase_array!(numbers, std::fmt::Display);
+/// assert_eq!(erased.len(), 4);
+/// assert_eq!(format!("{}", erased[2]), "3");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_array {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            fn __whiteout_erase<T: $($tr)*, const N: usize>(val: [T; N]) -> [impl $($tr)*; N] {
+                val
+            }
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// `erase_repeat!(value, trait; N)` erases a value that is `Clone` and
+/// produces an array of `N` copies of it, whose elements share one anonymous
+/// type that is `impl Trait`.
+///
+/// Unlike `[value; N]`, this doesn't need the erased type to be `Copy`, or the
+/// trait to include `Clone`. `N` must be a constant expression.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let buffer = erase_repeat!(3, MyTrait; 5);
+/// assert_eq!(buffer.len(), 5);
+/// let [a, b, c, d, e] = buffer;
+/// assert_eq!(a + b + c + d + e, 15.into());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_repeat {
+    (@trait [$val:expr] [$($tr:tt)*] ; $n:expr) => {
+        {
+            fn __whiteout_erase<T: $($tr)* + Clone>(val: T) -> [impl $($tr)*; $n] {
+                $crate::__private::array_from_fn(|_| val.clone())
+            }
+            __whiteout_erase($val)
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erase_repeat!(@trait [$val] [$($tr)* $next] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::erase_repeat!(@trait [$val] [] $($rest)*)
+    };
+}
+
+
+/// `erase_add!(a, b, trait)` erases both operands through a single eraser
+/// function and adds them.
+///
+/// The sum has the type given by the trait's `Add` bound. If that bound is
+/// `Add<Self, Output=Self>`, the sum has the same anonymous type as the
+/// operands, so a chain of additions stays opaque; other values of that type
+/// can be made with any constructor the trait provides, such as `From`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// trait MyTrait:
+///     std::ops::Add<Self, Output=Self>  // The sum is the erased type itself
+///     + std::convert::From<i32>
+///     + std::fmt::Debug
+///     + PartialEq
+///     {}
+///
+/// impl<T> MyTrait for T
+///     where T: std::ops::Add<Self, Output=Self>
+///     + std::convert::From<i32>
+///     + std::fmt::Debug
+///     + PartialEq
+///     {}
+///
+///# fn main() {
+/// let sum = erase_add!(1, 2, MyTrait);
+/// // A third value of the same erased type, added to the erased sum
+/// let total = sum + 3.into();
+/// assert_eq!(total, 6.into());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_add {
+    ($a:expr, $b:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            __whiteout_erase($a) + __whiteout_erase($b)
+        }
+    }
+}
+
+
+/// `try_eraser!(name, target => trait)` creates a function with the given
+/// identifier that converts values to the target type with `TryInto`, then
+/// erases the result to an anonymous type that is `impl Trait` for the given
+/// trait.
+///
+/// The generated function returns a `Result`, with the conversion's error
+/// type as its error, so failed conversions are surfaced to the caller.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// // Generates fn erase_byte<T: TryInto<u8>>(val: T) -> Result<impl Display, T::Error>
+/// try_eraser!(erase_byte, u8 => std::fmt::Display);
+///
+/// fn main() {
+///     let a = erase_byte(10i32).unwrap();
+///     assert_eq!(format!("{}", a), "10");
+///     assert!(erase_byte(300i32).is_err());
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! try_eraser {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $target:ty => $($tr:tt)*) => {
+        $(#[$attr])*
+        $vis fn $name<T: $crate::__private::TryInto<$target>>(val: T)
+            -> Result<impl $($tr)*, <T as $crate::__private::TryInto<$target>>::Error>
+        {
+            $crate::__private::TryInto::try_into(val)
+        }
+    }
+}
+
+
+/// `erase_vec!(vec, trait)` erases every element of a `Vec` of any type that
+/// implements trait, producing a `Vec` whose elements share one anonymous
+/// type that is `impl Trait`.
+///
+/// Like the other erasers, this is the identity: the elements are not
+/// boxed, and the `Vec` is not rebuilt.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let mut erased = erase_vec!(vec![1, 2, 3, 4], MyTrait).into_iter();
+/// let first = erased.next().unwrap();
+/// let sum = erased.fold(first, |a, b| a + b);
+/// assert_eq!(sum, 10.into());
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! erase_vec {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            fn __whiteout_erase<T: $($tr)*>(val: $crate::__private::Vec<T>) -> $crate::__private::Vec<impl $($tr)*> {
+                val
+            }
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// An extension trait for passing values through eraser functions fluently.
+///
+/// A method like `value.erase::<MyTrait>()` can't be written, since Rust has
+/// no way to be generic over traits, so the eraser for a trait still has to
+/// come from a macro such as `eraser!`. `erase_with` is the closest
+/// alternative, letting an eraser be applied at the end of a method chain.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use whiteout::Erase;
+///
+/// eraser!(erase_debug, std::fmt::Debug);
+///
+/// fn main() {
+///     let a = vec![3, 1, 2].into_iter().rev().collect::<Vec<_>>().erase_with(erase_debug);
+///     assert_eq!(format!("{:?}", a), "[2, 1, 3]");
+/// }
+/// ```
+///
+pub trait Erase: Sized {
+    /// Passes `self` through the given eraser, returning the erased value.
+    fn erase_with<F, R>(self, eraser: F) -> R
+        where F: FnOnce(Self) -> R
+    {
+        eraser(self)
+    }
+}
+
+impl<T> Erase for T {}
+
+
+/// Returns the default value of the given value's type. For an erased value
+/// whose trait includes `Default`, this makes a fresh value of the same
+/// anonymous type without naming it.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// fn main() {
+///     let a = erase!(5, std::fmt::Debug + Default);
+///     let zero = whiteout::default_like(&a);
+///     assert_eq!(format!("{:?} {:?}", a, zero), "5 0");
+/// }
+/// ```
+///
+pub fn default_like<T: Default>(_: &T) -> T {
+    T::default()
+}
+
+
+/// `cow_erase!(value, trait)` erases a value into an owned
+/// `whiteout::erased::ErasedCow<dyn Trait>`, and `cow_erase!(&value, trait)`
+/// erases a reference into a borrowed one.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let name = String::from("borrowed");
+/// let a = cow_erase!(&name, std::fmt::Display);
+/// let b = cow_erase!(5, std::fmt::Display);
+/// assert!(a.is_borrowed());
+/// assert!(b.is_owned());
+/// assert_eq!(format!("{} {}", &*a, &*b), "borrowed 5");
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! cow_erase {
+    (&$val:expr, $($tr:tt)*) => {
+        {
+            let cow: $crate::erased::ErasedCow<$crate::__dyn!($($tr)*)> = $crate::erased::ErasedCow::Borrowed(&$val);
+            cow
+        }
+    };
+    ($val:expr, $($tr:tt)*) => {
+        {
+            let cow: $crate::erased::ErasedCow<$crate::__dyn!($($tr)*)> =
+                $crate::erased::ErasedCow::Owned($crate::__private::Box::new($val));
+            cow
+        }
+    };
+}
+
+
+/// `eraser_fn!(name, (args...) -> ret)` creates a function with the given
+/// identifier that erases any closure or function with the given call
+/// signature to an anonymous type that is `impl Fn(args...) -> ret`.
+///
+/// The return type may be left out for closures returning `()`. As with
+/// `eraser!`, the name may be preceded by a visibility and attributes.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// eraser_fn!(erase_op, (i32, i32) -> i32);
+///
+///# fn main() {
+/// let offset = 10;
+/// let add = erase_op(move |a, b| a + b + offset);
+/// assert_eq!(add(1, 2), 13);
+/// assert_eq!(add(3, 4), 17);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_fn {
+    ($(#[$attr:meta])* $vis:vis $name:ident, ($($arg:ty),* $(,)?) $(-> $ret:ty)?) => {
+        $(#[$attr])*
+        $vis fn $name<F: Fn($($arg),*) $(-> $ret)?>(f: F) -> impl Fn($($arg),*) $(-> $ret)? {
+            f
+        }
+    }
+}
+
+
+/// `erase_if!(cfg, value, trait)` erases the value like `erase!` when the
+/// given `cfg` predicate holds, and leaves it as its concrete type otherwise.
+///
+/// The call site is the same in both builds, so code which only uses the
+/// trait compiles either way.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// // Concrete in debug builds, erased in release builds
+/// let a = erase_if!(not(debug_assertions), 5, std::fmt::Debug);
+/// assert_eq!(format!("{:?}", a), "5");
+///
+/// // `any()` never holds, so this keeps its concrete type
+/// let b = erase_if!(any(), 5i32, std::fmt::Debug);
+/// assert_eq!(b.pow(2), 25);
+///# }
+/// ```
+///
+/// When the predicate holds, only the trait is visible:
+///
+/// ```compile_fail,E0599
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// // `all()` always holds, so this is erased
+/// let c = erase_if!(all(), 5i32, std::fmt::Debug);
+/// assert_eq!(c.pow(2), 25);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_if {
+    ($cfg:meta, $val:expr, $($tr:tt)*) => {
+        {
+            #[cfg($cfg)]
+            let val = $crate::erase!($val, $($tr)*);
+            #[cfg(not($cfg))]
+            let val = $val;
+            val
+        }
+    }
+}
+
+
+/// `erase_toggle!(const true, value, trait)` erases the value like `erase!`,
+/// and `erase_toggle!(const false, value, trait)` leaves it as its concrete
+/// type.
+///
+/// This is like `erase_if!`, but switched by a flag rather than a `cfg`, so
+/// that a library can build both an opaque and a transparent API from one
+/// source. Only a literal `true` or `false` can change the type of the
+/// result, though: with any other flag, such as a const generic parameter,
+/// the macro branches with `if` at run time, and both branches have to give
+/// one type, since a generic function has one return type for every value of
+/// its parameters. So the result is a `whiteout::erased::Toggled`, holding
+/// either the erased value or the concrete one; for a constant flag, only
+/// the branch taken is left after optimization.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase_toggle!(const true, 5i32, std::fmt::Debug);
+/// assert_eq!(format!("{:?}", a), "5");
+///
+/// let b = erase_toggle!(const false, 5i32, std::fmt::Debug);
+/// assert_eq!(b.pow(2), 25);
+///# }
+/// ```
+///
+/// With a const generic flag:
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// use whiteout::erased::Toggled;
+///
+/// fn wrap<const ERASE: bool>(val: i32) -> Toggled<impl std::fmt::Debug, i32> {
+///     erase_toggle!(const ERASE, val, std::fmt::Debug)
+/// }
+///
+///# fn main() {
+/// assert!(wrap::<true>(5).is_erased());
+/// assert_eq!(wrap::<false>(5).concrete(), Some(5));
+///# }
+/// ```
+///
+/// When the flag is `true`, only the trait is visible:
+///
+/// ```compile_fail,E0599
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let c = erase_toggle!(const true, 5i32, std::fmt::Debug);
+/// assert_eq!(c.pow(2), 25);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_toggle {
+    (const true, $val:expr, $($tr:tt)*) => {
+        $crate::erase!($val, $($tr)*)
+    };
+    (const false, $val:expr, $($tr:tt)*) => {
+        $val
+    };
+    (const $flag:expr, $val:expr, $($tr:tt)*) => {
+        {
+            let val = $val;
+            if $flag {
+                $crate::erased::Toggled::Erased($crate::erase!(val, $($tr)*))
+            } else {
+                $crate::erased::Toggled::Concrete(val)
+            }
+        }
+    };
+}
+
+
+/// `erasers! { name => trait, ... }` creates one eraser function per entry,
+/// exactly as `eraser!(name, trait)` would.
+///
+/// Each entry may have its own visibility and attributes, and traits may
+/// contain commas inside angle brackets.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// erasers! {
+///     erase_debug => std::fmt::Debug,
+///     /// Erases an iterator of results
+///     #[inline]
+///     pub erase_results => Iterator<Item = Result<u8, ()>>,
+///     erase_display => std::fmt::Display,
+/// }
+///
+///# fn main() {
+/// let results = erase_results(vec![Ok(1), Err(()), Ok(2)].into_iter());
+/// assert_eq!(results.filter_map(Result::ok).sum::<u8>(), 3);
+/// assert_eq!(format!("{:?}", erase_debug("x")), "\"x\"");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erasers {
+    // A comma outside any angle brackets ends the entry
+    (@trait [$($head:tt)*] [$($tr:tt)*] [] , $($rest:tt)*) => {
+        $crate::eraser!($($head)*, $($tr)*);
+        $crate::erasers!($($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::erasers!(@trait [$($head)*] [$($tr)* <] [< $($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::erasers!(@trait [$($head)*] [$($tr)* >] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::erasers!(@trait [$($head)*] [$($tr)* >>] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erasers!(@trait [$($head)*] [$($tr)* $next] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] []) => {
+        $crate::eraser!($($head)*, $($tr)*);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident => $($rest:tt)*) => {
+        $crate::erasers!(@trait [$(#[$attr])* $vis $name] [] [] $($rest)*);
+    };
+    () => {};
+}
+
+
+/// `scoped_eraser!(trait, |e| body)` creates an eraser for the given trait
+/// that is only visible in `body`, where it is bound to `e`.
+///
+/// Like a function made by `eraser!`, `e` can be called many times, and the
+/// values it returns share one anonymous type as long as the inputs do. The
+/// macro evaluates to the value of `body`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let sum = scoped_eraser!(MyTrait, |e| {
+///     let a = e(1);
+///     let b = e(2);
+///     a + b
+/// });
+/// assert_eq!(sum, 3.into());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! scoped_eraser {
+    (@trait [$($tr:tt)*] , | $e:ident | $body:expr) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            let $e = __whiteout_erase;
+            $body
+        }
+    };
+    (@trait [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::scoped_eraser!(@trait [$($tr)* $next] $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        $crate::scoped_eraser!(@trait [] $($rest)*)
+    };
+}
+
+
+/// `loop_eraser!(trait)` evaluates to an eraser for the given trait, to be
+/// bound with `let` before a loop and called inside it.
+///
+/// A fresh `erase!` in the body of a loop is still one eraser, but values
+/// erased by different `erase!`s in the same loop, or values made before
+/// and inside the loop, have different types. Every value erased by one
+/// `loop_eraser!` has the same anonymous type, so they can all be collected
+/// into one `Vec<impl Trait>`. The eraser is a single instance of a generic
+/// function, so it only takes values of the type of its first argument.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let e = loop_eraser!(MyTrait);
+/// let mut values = vec![e(10)];
+/// for i in 1..4 {
+///     values.push(e(i));
+/// }
+/// let sum = values.into_iter().reduce(|a, b| a + b).unwrap();
+/// assert_eq!(sum, 16.into());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! loop_eraser {
+    ($($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            __whiteout_erase
+        }
+    }
+}
+
+/// `eraser_with_default!(name, default_name: Type, trait)` creates an eraser
+/// like `eraser!(name, trait)` whose erased type also implements `Default`,
+/// along with a macro `default_name!()` that erases `Type::default()` through
+/// the same eraser.
+///
+/// Since `default_name!()` calls `name` on a `Type`, its value has exactly the
+/// same anonymous type as `name(x)` for any `x: Type`, so the two can be
+/// combined. A separate function can't do this on stable: each function's
+/// `impl Trait` return type is distinct. Like any `macro_rules!` macro,
+/// `default_name!` is usable after the call in the same module.
+///
+/// `Default` is added to the trait before any `where` clause. Only types
+/// implementing `Default` can be passed to the eraser.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+/// eraser_with_default!(erase_num, zero_num: i32, MyTrait);
+///
+/// fn main() {
+///     let a = erase_num(5);
+///     let zero = zero_num!();
+///     assert_eq!(zero, 0.into());
+///     assert_eq!(a + zero, 5.into());
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_with_default {
+    (@split [$($head:tt)*] [$default:ident $chosen:ty] [$($tr:tt)*] where $($rest:tt)*) => {
+        $crate::eraser_with_default!(@emit [$($head)*] [$default $chosen] [$($tr)*] where $($rest)*);
+    };
+    (@split [$($head:tt)*] [$default:ident $chosen:ty] [$($tr:tt)*] + use $($rest:tt)*) => {
+        $crate::eraser_with_default!(@emit [$($head)*] [$default $chosen] [$($tr)*] + use $($rest)*);
+    };
+    (@split [$($head:tt)*] [$default:ident $chosen:ty] [$($tr:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::eraser_with_default!(@split [$($head)*] [$default $chosen] [$($tr)* $t] $($rest)*);
+    };
+    (@split [$($head:tt)*] [$default:ident $chosen:ty] [$($tr:tt)*]) => {
+        $crate::eraser_with_default!(@emit [$($head)*] [$default $chosen] [$($tr)*]);
+    };
+    (@emit [$(#[$attr:meta])* $vis:vis $name:ident] [$default:ident $chosen:ty] [$($tr:tt)*] $($rest:tt)*) => {
+        $crate::eraser!($(#[$attr])* $vis $name, $($tr)* + $crate::__private::Default $($rest)*);
+        macro_rules! $default {
+            () => { $name(<$chosen as $crate::__private::Default>::default()) };
+        }
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident, $default:ident : $chosen:ty, $($tr:tt)*) => {
+        $crate::eraser_with_default!(@split [$(#[$attr])* $vis $name] [$default $chosen] [] $($tr)*);
+    };
+}
+
+
+/// `erase_into_boxed_slice!([values...], trait)` boxes every value in the
+/// list as a trait object, collecting them into a
+/// `Box<[Box<dyn Trait>]>`.
+///
+/// Unlike `erase_all!`, the values may be of different types. A boxed slice
+/// is one word smaller than a `Vec` and can't grow, which suits fixed sets of
+/// values.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let values = erase_into_boxed_slice!([10, 2.5, "hi"], std::fmt::Display);
+/// let shown: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+/// assert_eq!(shown, ["10", "2.5", "hi"]);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! erase_into_boxed_slice {
+    ([$($val:expr),* $(,)?], $($tr:tt)*) => {
+        {
+            fn __whiteout_erase(val: $crate::__private::Box<$crate::__dyn!($($tr)*)>) -> $crate::__private::Box<$crate::__dyn!($($tr)*)> {
+                val
+            }
+            let boxed: $crate::__private::Box<[$crate::__private::Box<$crate::__dyn!($($tr)*)>]> =
+                $crate::__private::Box::new([$(__whiteout_erase($crate::__private::Box::new($val))),*]);
+            boxed
+        }
+    }
+}
+
+
+/// `erase_err!(result, trait)` boxes the `Err` value of a `Result` as a trait
+/// object, producing a `Result<T, Box<dyn Trait>>`.
+///
+/// This unifies results with different error types, so they can be returned
+/// from different branches or kept in one collection. The `Ok` value is left
+/// as it was; `erase_ok!` erases that side instead.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::error::Error;
+///
+/// fn parse(s: &str, float: bool) -> Result<f64, Box<dyn Error>> {
+///     if float {
+///         erase_err!(s.parse::<f64>(), Error)
+///     } else {
+///         erase_err!(s.parse::<i32>().map(f64::from), Error)
+///     }
+/// }
+/// assert_eq!(parse("1.5", true).unwrap(), 1.5);
+/// assert_eq!(parse("2", false).unwrap(), 2.0);
+/// assert_eq!(parse("x", true).unwrap_err().to_string(), "invalid float literal");
+/// assert_eq!(parse("x", false).unwrap_err().to_string(), "invalid digit found in string");
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! erase_err {
+    ($val:expr, $($tr:tt)*) => {
+        $val.map_err(|err| $crate::boxerase!(err, $($tr)*))
+    }
+}
+
+
+/// `eraser_capturing!(name<'a>, trait)` creates a function with the given
+/// identifier that erases a reference `&'a T` to an anonymous type that is
+/// `impl Trait`, for any `T` whose references implement trait.
+///
+/// Where `eraser_ref!` returns `&impl Trait` for a `T` implementing trait,
+/// this erases the reference itself, giving an owned value that borrows from
+/// the original. The trait should usually include `+ 'a`, so the erased value
+/// may outlive the function call.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// eraser_capturing!(erase_view<'a>, IntoIterator<Item = &'a i32> + 'a);
+///
+/// fn main() {
+///     let v = vec![1, 2, 3];
+///     let view = erase_view(&v);
+///     let sum: i32 = view.into_iter().sum();
+///     assert_eq!(sum, 6);
+///     assert_eq!(erase_view(&[4, 5]).into_iter().count(), 2);
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_capturing {
+    ($(#[$attr:meta])* $vis:vis $name:ident < $lt:lifetime >, $($tr:tt)*) => {
+        $(#[$attr])*
+        $vis fn $name<$lt, T: ?Sized + $lt>(val: &$lt T) -> impl $($tr)*
+            where &$lt T: $($tr)*
+        {
+            val
+        }
+    }
+}
+
+
+/// `erase_with_type_name!(value, trait)` erases a value like `erase!`. With
+/// the `debug-typenames` feature, it also records the name of the value's
+/// type before erasure, returning a `whiteout::erased::TypeNamed` whose
+/// `type_name()` gives the name.
+///
+/// Without the feature, this is exactly `erase!`. Either way the trait's
+/// methods can be called on the result, and it can be formatted if the trait
+/// allows.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase_with_type_name!(vec![1, 2], std::fmt::Debug + Clone);
+/// assert_eq!(format!("{:?}", a.clone()), "[1, 2]");
+/// #[cfg(feature = "debug-typenames")]
+/// assert_eq!(a.type_name(), "alloc::vec::Vec<i32>");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_with_type_name {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::__erase_with_type_name!($val, $($tr)*)
+    }
+}
+
+#[cfg(feature = "debug-typenames")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __erase_with_type_name {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            $crate::erased::TypeNamed::new($val).map(__whiteout_erase)
+        }
+    }
+}
+
+#[cfg(not(feature = "debug-typenames"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __erase_with_type_name {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erase!($val, $($tr)*)
+    }
+}
+
+
+/// `erase_logged!(value, trait)` erases a value like `erase!`. With the
+/// `logging` feature, it also logs the name of the value's type with
+/// `log::trace!`, which helps to find out what was erased where, as in
+/// plugin systems.
+///
+/// Without the feature, this is exactly `erase!`, so it costs nothing. The
+/// macro names `log` by its bare path, so with the feature on, the crate
+/// using it must depend on `log`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# #[macro_export]
+///# macro_rules! trace { ($($arg:tt)*) => { let _ = format!($($arg)*); } }
+///# mod log { pub use trace; }
+///# fn main() {
+/// let a = erase_logged!(vec![1, 2], std::fmt::Debug);
+/// assert_eq!(format!("{:?}", a), "[1, 2]");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_logged {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::__erase_logged!($val, $($tr)*)
+    }
+}
+
+#[cfg(feature = "logging")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __erase_logged {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            fn __whiteout_log<T>(val: T) -> T {
+                log::trace!("erasing a value of type {}", $crate::__private::type_name::<T>());
+                val
+            }
+            $crate::erase!(__whiteout_log($val), $($tr)*)
+        }
+    }
+}
+
+#[cfg(not(feature = "logging"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __erase_logged {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erase!($val, $($tr)*)
+    }
+}
+
+
+/// `reerase!(value, trait)` erases an already erased value again, to a trait
+/// that it still implements, such as a subset of the traits it was erased to.
+///
+/// The result has a new anonymous type which only implements the new trait.
+/// This is the same as `erase!`, but says what is meant at the call site.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(vec![1, 2], std::fmt::Debug + Clone);
+/// let b = reerase!(a.clone(), std::fmt::Debug);
+/// assert_eq!(format!("{:?} {:?}", a, b), "[1, 2] [1, 2]");
+///# }
+/// ```
+///
+/// The traits left out are no longer available:
+///
+/// ```compile_fail,E0599
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase!(vec![1, 2], std::fmt::Debug + Clone);
+/// let b = reerase!(a, std::fmt::Debug);
+/// let c = b.clone();
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! reerase {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erase!($val, $($tr)*)
+    }
+}
+
+
+/// `eraser_method!(Type, name, trait)` creates an eraser like
+/// `eraser!(name, trait)` as an associated function of the given type, which
+/// must be defined in the current crate, so that it is called as
+/// `Type::name(value)`.
+///
+/// Everything `eraser!` accepts after the type is supported, including a
+/// visibility and attributes before the name.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+/// struct Erasers;
+///
+/// eraser_method!(Erasers, pub erase, MyTrait);
+///
+/// fn main() {
+///     let a = Erasers::erase(1);
+///     let b = Erasers::erase(2);
+///     assert_eq!(a + b, 3.into());
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_method {
+    ($ty:ty, $($rest:tt)*) => {
+        impl $ty {
+            $crate::eraser!($($rest)*);
+        }
+    }
+}
+
+
+/// `clone_erase!(value, trait)` boxes a value of any type that implements
+/// trait and `Clone` into a `whiteout::erased::CloneableErased<dyn Trait>`,
+/// which can be cloned even though `dyn Trait` can't.
+///
+/// The trait must be object safe, and the value must be `'static`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = clone_erase!(vec![1, 2], std::fmt::Debug);
+/// let b = clone_erase!("three", std::fmt::Debug);
+/// let mut values = vec![a, b];
+/// let copy = values[0].clone();
+/// values.push(copy);
+/// let shown: Vec<String> = values.iter().map(|v| format!("{:?}", &**v)).collect();
+/// assert_eq!(shown, ["[1, 2]", "\"three\"", "[1, 2]"]);
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! clone_erase {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            // The concrete type is only known here, so the coercion to the
+            // trait object has to be written here too
+            #[derive(Clone)]
+            struct __WhiteoutHolder<T>(T);
+
+            impl<T: $($tr)* + Clone + 'static> $crate::erased::CloneErase<$crate::__dyn!($($tr)*)> for __WhiteoutHolder<T> {
+                fn get(&self) -> &$crate::__dyn!($($tr)* + 'static) {
+                    &self.0
+                }
+
+                fn clone_box(&self) -> $crate::__private::Box<dyn $crate::erased::CloneErase<$crate::__dyn!($($tr)*)>> {
+                    $crate::__private::Box::new(self.clone())
+                }
+            }
+
+            $crate::erased::CloneableErased::<$crate::__dyn!($($tr)*)>::new(__WhiteoutHolder($val))
+        }
+    }
+}
+
+
+/// `small_erase!(value, trait)` stores a value of any type that implements
+/// trait in a `whiteout::small::SmallErased<dyn Trait, N>`, inline if it fits
+/// in `N` words and boxed otherwise.
+///
+/// `N` is usually inferred from where the value is used. The value must be
+/// `'static`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use whiteout::small::SmallErased;
+///
+/// let small: SmallErased<dyn std::fmt::Display, 2> = small_erase!(10, std::fmt::Display);
+/// let large: SmallErased<dyn std::fmt::Display, 2> = small_erase!("a".repeat(3), std::fmt::Display);
+/// assert!(small.is_inline());
+/// assert!(!large.is_inline());
+/// assert_eq!(format!("{} {}", &*small, &*large), "10 aaa");
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! small_erase {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::small::SmallErased::<$crate::__dyn!($($tr)*), _>::new($val, |val| val)
+    }
+}
+
+
+/// `erase_map_values!(map, trait)` erases every value of a `HashMap` whose
+/// values are of any type that implements trait, producing a `HashMap` whose
+/// values share one anonymous type that is `impl Trait`.
+///
+/// The keys and hasher are untouched. Like `erase_vec!`, this is the identity,
+/// so the map is not rebuilt.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// use std::collections::HashMap;
+///
+/// let mut scores = HashMap::new();
+/// scores.insert(String::from("a"), 1);
+/// scores.insert(String::from("b"), 2);
+/// let scores = erase_map_values!(scores, MyTrait);
+/// let mut values = scores.into_iter().map(|(_, v)| v);
+/// let first = values.next().unwrap();
+/// let total = values.fold(first, |a, b| a + b);
+/// assert_eq!(total, 3.into());
+///# }
+/// ```
+///
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! erase_map_values {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            fn __whiteout_erase<K, V: $($tr)*, S>(val: $crate::__private::HashMap<K, V, S>)
+                -> $crate::__private::HashMap<K, impl $($tr)*, S>
+            {
+                val
+            }
+            __whiteout_erase($val)
+        }
+    }
+}
+
+
+/// `erase_map_keys!(map, trait)` erases every key of a `HashMap` through one
+/// eraser for the trait, rebuilding the map with the same hasher so that its
+/// keys share one anonymous type. It evaluates to the new map and the eraser.
+///
+/// The trait has to include `Hash + Eq`. An erased key hashes and compares
+/// exactly like the key it came from. To look an entry up, though, the key
+/// has to be erased to the map's key type, and nothing but the eraser made
+/// inside the macro gives that type; so it's returned alongside the map, to
+/// erase keys for lookups. It only takes keys of the map's original type.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// use std::collections::HashMap;
+///
+/// let mut names = HashMap::new();
+/// names.insert(1, "one");
+/// names.insert(2, "two");
+/// let (names, erase_key) = erase_map_keys!(names, MyTrait + std::hash::Hash + Eq);
+/// assert_eq!(names.get(&erase_key(2)), Some(&"two"));
+/// assert_eq!(names.get(&erase_key(3)), None);
+///# }
+/// ```
+///
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! erase_map_keys {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            let map = $val;
+            let mut erased = $crate::__private::HashMap::with_capacity_and_hasher(
+                map.len(),
+                map.hasher().clone(),
+            );
+            for (key, value) in map {
+                erased.insert(__whiteout_erase(key), value);
+            }
+            (erased, __whiteout_erase)
+        }
+    }
+}
+
+
+/// `erase_fields!(value, trait, [fields...])` erases the listed fields of a
+/// struct through a single eraser function, producing a tuple with one erased
+/// value per field, in the order listed.
+///
+/// As with `erase_all!`, fields of the same type come out with the same
+/// anonymous type, so they can be used together. The struct is moved, and any
+/// fields not listed are dropped.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// struct Point {
+///     x: i32,
+///     y: i32,
+///     label: &'static str,
+/// }
+///
+/// let p = Point { x: 1, y: 2, label: "p" };
+/// assert_eq!(p.label, "p");
+/// let (x, y) = erase_fields!(p, MyTrait, [x, y]);
+/// assert_eq!(x + y, 3.into());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_fields {
+    (@trait [$val:expr] [$($tr:tt)*] , [$($field:ident),+ $(,)?]) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            let val = $val;
+            ($(__whiteout_erase(val.$field),)+)
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erase_fields!(@trait [$val] [$($tr)* $next] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::erase_fields!(@trait [$val] [] $($rest)*)
+    };
+}
+
+
+/// `replace_erase!(place, value, eraser)` erases the value with the given
+/// eraser function and puts it in the place, returning the value that was
+/// there before.
+///
+/// Every eraser makes its own anonymous type, so an eraser generated inside
+/// this macro from a trait would give the new value a type the place can't
+/// hold. The type is fixed by whichever eraser filled the place first, and
+/// only calling that eraser again gives a value of it; so the eraser, such
+/// as a function made by `eraser!`, is passed in place of a trait.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+/// eraser!(erase_my_trait, MyTrait);
+///
+/// struct Slot<T> {
+///     value: T,
+/// }
+///
+/// fn main() {
+///     let mut slot = Slot { value: erase_my_trait(1) };
+///     let old = replace_erase!(slot.value, 10, erase_my_trait);
+///     assert_eq!(old, 1.into());
+///     assert_eq!(slot.value, 10.into());
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! replace_erase {
+    ($place:expr, $val:expr, $eraser:expr) => {
+        $crate::__private::replace(&mut $place, $eraser($val))
+    }
+}
+
+
+/// `deref_erase!(value, trait)` boxes a value of any type that implements
+/// trait into a `whiteout::erased::DerefErased<dyn Trait>`, which derefs to
+/// `dyn Trait`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// trait Describe {
+///     fn describe(&self) -> String;
+/// }
+///
+/// impl Describe for i32 {
+///     fn describe(&self) -> String { format!("int {}", self) }
+/// }
+///
+/// let a = deref_erase!(10, Describe);
+/// assert_eq!((*a).describe(), "int 10");
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! deref_erase {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erased::DerefErased::new($crate::boxerase!($val, $($tr)*))
+    }
+}
+
+
+/// `from_erase!(value, type, trait)` converts the value into the given type
+/// with `From`, then erases the result like `erase!`.
+///
+/// This saves annotating a conversion whose target type can't be inferred,
+/// since the eraser accepts any type.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let a = from_erase!(1u8, i64, MyTrait);
+/// let b = a + 2.into();
+/// assert_eq!(b, 3.into());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! from_erase {
+    ($val:expr, $target:ty, $($tr:tt)*) => {
+        $crate::erase!(<$target as $crate::__private::From<_>>::from($val), $($tr)*)
+    }
+}
+
+
+/// `erase_manual!(value, trait)` erases a value like `erase!`, wrapping it in
+/// a `ManuallyDrop` so that it is not dropped automatically.
+///
+/// The erased value can be used through the `ManuallyDrop`, and must be
+/// dropped with `whiteout::ffi::drop_erased`, or it is leaked. See the `ffi`
+/// module.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let handle = erase_manual!(String::from("held"), std::fmt::Display);
+/// assert_eq!(handle.to_string(), "held");
+/// whiteout::ffi::drop_erased(handle);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_manual {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::__private::ManuallyDrop::new($crate::erase!($val, $($tr)*))
+    }
+}
+
+
+/// `erase_enum!(value, trait, Enum { Variant(binding) => payload, ... })`
+/// matches on an enum, erasing the payload of whichever variant it holds
+/// through a single eraser function.
+///
+/// Every arm gives an erased value of the same anonymous type, as long as the
+/// payloads are of the same type, so the match has one type. Payloads of
+/// different types can be converted in their arms. Each variant must have
+/// fields; match unit variants separately.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// enum Number {
+///     Int(i32),
+///     Byte(u8),
+/// }
+///
+/// let erase_number = |n| erase_enum!(n, MyTrait, Number {
+///     Int(x) => x,
+///     Byte(y) => i32::from(y),
+/// });
+///
+/// let a = erase_number(Number::Int(10));
+/// let b = erase_number(Number::Byte(5));
+/// assert_eq!(a + b, 15.into());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_enum {
+    // Build the arms one at a time, since the enum's path is repeated in each
+    (@arms [$val:expr] [$($tr:tt)*] [$($en:ident)::+] [$($arms:tt)*]
+        $var:ident $fields:tt => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::erase_enum!(@arms [$val] [$($tr)*] [$($en)::+]
+            [$($arms)* $($en)::+ :: $var $fields => __whiteout_erase($body),] $($($rest)*)?)
+    };
+    (@arms [$val:expr] [$($tr:tt)*] [$($en:ident)::+] [$($arms:tt)*]) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            match $val {
+                $($arms)*
+            }
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] , $($en:ident)::+ { $($arms:tt)* }) => {
+        $crate::erase_enum!(@arms [$val] [$($tr)*] [$($en)::+] [] $($arms)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erase_enum!(@trait [$val] [$($tr)* $next] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::erase_enum!(@trait [$val] [] $($rest)*)
+    };
+}
+
+
+/// `match_box_erase!(value, trait, { pattern => payload, ... })` matches on a
+/// value and boxes the payload of whichever arm matches as a
+/// `Box<dyn Trait>`.
+///
+/// This is the boxed counterpart of `erase_enum!`: the payloads of the arms
+/// can be of different types, since each is coerced to the same trait
+/// object. The trait must be object safe. The patterns are written in full,
+/// so they can match any type, not only an enum's variants.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// enum Value {
+///     Int(i32),
+///     Text(String),
+/// }
+///
+/// let show = |v| {
+///     let shown = match_box_erase!(v, std::fmt::Debug, {
+///         Value::Int(x) => x,
+///         Value::Text(s) => s,
+///     });
+///     format!("{:?}", shown)
+/// };
+///
+/// assert_eq!(show(Value::Int(3)), "3");
+/// assert_eq!(show(Value::Text("three".to_string())), "\"three\"");
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! match_box_erase {
+    // Build the arms one at a time, since the trait is repeated in each
+    (@arms [$val:expr] [$($tr:tt)*] [$($arms:tt)*] $pat:pat => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::match_box_erase!(@arms [$val] [$($tr)*]
+            [$($arms)* $pat => $crate::boxerase!($body, $($tr)*),] $($($rest)*)?)
+    };
+    (@arms [$val:expr] [$($tr:tt)*] [$($arms:tt)*]) => {
+        match $val {
+            $($arms)*
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] , { $($arms:tt)* }) => {
+        $crate::match_box_erase!(@arms [$val] [$($tr)*] [] $($arms)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::match_box_erase!(@trait [$val] [$($tr)* $next] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::match_box_erase!(@trait [$val] [] $($rest)*)
+    };
+}
+
+
+/// `erase_pair!(a, b, trait)` erases two values through a single eraser
+/// function, like `tuple_erase!((a, b), trait)`, so that two values of the
+/// same type come out with the same anonymous type and can be used as the
+/// operands of binary operations.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// trait Operand: std::ops::Add<Self, Output=Self> + PartialOrd + std::fmt::Debug + Sized {}
+/// impl<T> Operand for T where T: std::ops::Add<T, Output=T> + PartialOrd + std::fmt::Debug {}
+///
+///# fn main() {
+/// let (a, b) = erase_pair!(1, 2, Operand);
+/// assert!(a < b);
+/// assert_eq!(format!("{:?}", a + b), "3");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_pair {
+    ($a:expr, $b:expr, $($tr:tt)*) => {
+        $crate::tuple_erase!(($a, $b), $($tr)*)
+    }
+}
+
+
+/// `eraser_impl!(name, trait)` creates an eraser like `eraser!(name, trait)`,
+/// but takes its argument as `impl Trait` rather than as a generic `T`:
+/// `fn name(val: impl Trait) -> impl Trait`.
+///
+/// The two forms behave the same, and this one reads more simply in
+/// documentation. The difference is that the type of the argument can't be
+/// given with a turbofish, as in `name::<i32>(value)`. A visibility and
+/// attributes may be given before the name.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+/// eraser_impl!(erase_impl, MyTrait);
+/// eraser!(erase_generic, MyTrait);
+///
+/// fn main() {
+///     let a = erase_impl(1) + erase_impl(2);
+///     let b = erase_generic(1) + erase_generic::<i32>(2);
+///     assert_eq!(a, 3.into());
+///     assert_eq!(b, 3.into());
+/// }
+/// ```
+///
+/// ```compile_fail,E0107
+///# #[macro_use]
+///# extern crate whiteout;
+/// eraser_impl!(erase_impl, std::fmt::Debug);
+///
+///# fn main() {
+/// let a = erase_impl::<i32>(1);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_impl {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $($tr:tt)*) => {
+        $(#[$attr])*
+        $vis fn $name(val: impl $($tr)*) -> impl $($tr)* {
+            val
+        }
+    }
+}
+
+
+/// `erase_collect!(iterable, trait => Container<_>)` erases every item of an
+/// iterable through a single eraser function, collecting the erased items
+/// into any container that implements `FromIterator`.
+///
+/// The items share one anonymous type, written as `_` in the container's
+/// type. The container may require more of its items than the trait does,
+/// such as `Ord` for a `BTreeSet`, in which case the trait has to include it.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// use std::collections::{BTreeSet, VecDeque};
+///
+/// let mut queue = erase_collect!(1..4, MyTrait => VecDeque<_>);
+/// assert_eq!(queue.pop_front(), Some(1.into()));
+/// assert_eq!(queue.pop_back(), Some(3.into()));
+/// assert_eq!(queue.len(), 1);
+///
+/// let set = erase_collect!(vec![3, 1, 3], Ord + std::fmt::Debug => BTreeSet<_>);
+/// assert_eq!(format!("{:?}", set), "{1, 3}");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_collect {
+    (@trait [$val:expr] [$($tr:tt)*] => $container:ty) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            $crate::__private::IntoIterator::into_iter($val)
+                .map(__whiteout_erase)
+                .collect::<$container>()
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erase_collect!(@trait [$val] [$($tr)* $next] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::erase_collect!(@trait [$val] [] $($rest)*)
+    };
+}
+
+
+/// `eraser_for_each!(trait => [name_a, name_b])` creates one eraser function
+/// for the given trait per name, each exactly as `eraser!(name, trait)`
+/// would.
+///
+/// Every eraser returns its own anonymous type, even for the same input, so
+/// values passed through different erasers can't be mixed up. This is handy
+/// for keeping apart values that mean different things but share a trait.
+/// Each name may have its own visibility and attributes.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+/// eraser_for_each!(MyTrait => [erase_meters, pub erase_seconds]);
+///
+///# fn main() {
+/// assert_eq!(erase_meters(1) + erase_meters(2), 3.into());
+/// assert_eq!(erase_seconds(1) + erase_seconds(2), 3.into());
+///# }
+/// ```
+///
+/// Values from different erasers have different types, even though both
+/// erased an `i32`:
+///
+/// ```compile_fail,E0308
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+/// eraser_for_each!(MyTrait => [erase_meters, erase_seconds]);
+///
+///# fn main() {
+/// let _ = erase_meters(1) + erase_seconds(1);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! eraser_for_each {
+    (@names [$($tr:tt)*] $(#[$attr:meta])* $vis:vis $name:ident $(, $($rest:tt)*)?) => {
+        $crate::eraser!($(#[$attr])* $vis $name, $($tr)*);
+        $crate::eraser_for_each!(@names [$($tr)*] $($($rest)*)?);
+    };
+    (@names [$($tr:tt)*]) => {};
+    (@trait [$($tr:tt)*] => [$($names:tt)*]) => {
+        $crate::eraser_for_each!(@names [$($tr)*] $($names)*);
+    };
+    (@trait [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::eraser_for_each!(@trait [$($tr)* $next] $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::eraser_for_each!(@trait [] $($rest)*);
+    };
+}
+
+
+/// `layout_erase!(value, trait)` erases a value like `erase!(value, trait)`
+/// and wraps it in a `LayoutErased`, which records the size and alignment of
+/// the value's type.
+///
+/// The erased type has the same layout as the original one, so the recorded
+/// layout is the one to allocate for when storing the value in a pool.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::fmt::Debug;
+/// use std::mem::{align_of, size_of};
+///
+/// let small = layout_erase!(7u8, Debug);
+/// let large = layout_erase!(7u64, Debug);
+/// assert_eq!(small.layout().size(), size_of::<u8>());
+/// assert_eq!(small.layout().align(), align_of::<u8>());
+/// assert_eq!(large.layout().size(), size_of::<u64>());
+/// assert_eq!(large.layout().align(), align_of::<u64>());
+///
+/// let views: [&dyn Debug; 2] = [&*small, &*large];
+/// assert_eq!(format!("{:?}", views), "[7, 7]");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! layout_erase {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erased::LayoutErased::new($crate::erase!($val, $($tr)*))
+    }
+}
+
+
+/// `erase_serialize!(value)` boxes a value of any type that implements
+/// `serde::Serialize` as a `Box<dyn erased_serde::Serialize>`, when the
+/// `serde` feature is on, so that values of different types can be kept and
+/// serialized together.
+///
+/// Further bounds for the trait object, such as `Send`, may follow the value.
+/// The macro names `erased_serde` by its bare path, so the crate using it
+/// must depend on `erased_serde`.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+/// extern crate erased_serde;
+/// extern crate serde_json;
+///
+/// fn main() {
+///     let values = vec![
+///         erase_serialize!(1u8),
+///         erase_serialize!("two"),
+///         erase_serialize!(vec![3.5]),
+///     ];
+///     assert_eq!(serde_json::to_string(&values).unwrap(), r#"[1,"two",[3.5]]"#);
+/// }
+/// ```
+///
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! erase_serialize {
+    ($val:expr) => {
+        $crate::boxerase!($val, erased_serde::Serialize)
+    };
+    ($val:expr, $($bounds:tt)+) => {
+        $crate::boxerase!($val, erased_serde::Serialize + $($bounds)+)
+    };
+}
+
+
+/// `assert_erasable!(type, trait)` checks at compile time that a type
+/// implements trait, so that it can be erased to it.
+///
+/// It expands to an item, so it can be used at module level or in a function
+/// body, and fails to compile with an error naming the type and the missing
+/// bound. This is useful for finding out which bound is missing before the
+/// value reaches an eraser in generic code. The type can't refer to generic
+/// parameters of an enclosing function.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+/// assert_erasable!(Vec<u8>, std::fmt::Debug + Clone);
+///
+///# fn main() {
+/// assert_erasable!(&'static str, std::fmt::Display);
+///# }
+/// ```
+///
+/// A type that doesn't implement the trait is rejected with "the trait bound
+/// is not satisfied", pointing at the type:
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+/// struct Opaque;
+///
+/// assert_erasable!(Opaque, std::fmt::Debug);
+///# fn main() {}
+/// ```
+///
+#[macro_export]
+macro_rules! assert_erasable {
+    ($ty:ty, $($tr:tt)*) => {
+        const _: () = {
+            fn assert_erasable<T: $($tr)*>() {}
+            fn check() {
+                assert_erasable::<$ty>();
+            }
+        };
+    }
+}
+
+
+/// `sealed_erase!(value, trait, [types])` erases a value like
+/// `erase!(value, trait)`, but only compiles if the value's type is one of
+/// the listed types.
+///
+/// The check uses a trait which is private to the expansion and only
+/// implemented for the listed types, so no other type can satisfy it. The
+/// types can't refer to generic parameters of an enclosing function.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = sealed_erase!(1u8, std::fmt::Debug, [u8, u16]);
+/// let b = sealed_erase!(2u16, std::fmt::Debug, [u8, u16]);
+/// assert_eq!(format!("{:?} {:?}", a, b), "1 2");
+///# }
+/// ```
+///
+/// Any other type is rejected, even if it implements the trait:
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let c = sealed_erase!(3u32, std::fmt::Debug, [u8, u16]);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! sealed_erase {
+    (@trait [$val:expr] [$($tr:tt)*] , [$($ty:ty),* $(,)?]) => {
+        {
+            trait __WhiteoutAllowed {}
+            $(impl __WhiteoutAllowed for $ty {})*
+            $crate::eraser!(__whiteout_erase, $($tr)* where T: __WhiteoutAllowed);
+            __whiteout_erase($val)
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::sealed_erase!(@trait [$val] [$($tr)* $next] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::sealed_erase!(@trait [$val] [] $($rest)*)
+    };
+}
+
+
+/// `sealed_eraser!(name, trait, Marker)` creates an eraser like
+/// `eraser!(name, trait)` which also requires the type of its argument to
+/// implement the marker trait.
+///
+/// The marker is only a bound and isn't part of the erased type, which is
+/// `impl Trait` for the trait alone. A library can keep the marker in a
+/// private module, so that only its own types can be erased, while the
+/// eraser is public. A visibility and attributes may be given before the
+/// name.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// mod shapes {
+///     mod seal {
+///         pub trait Sealed {}
+///     }
+///
+///     pub trait Area {
+///         fn area(&self) -> f64;
+///     }
+///
+///     pub struct Square(pub f64);
+///     pub struct Circle(pub f64);
+///
+///     impl Area for Square {
+///         fn area(&self) -> f64 { self.0 * self.0 }
+///     }
+///     impl Area for Circle {
+///         fn area(&self) -> f64 { 3.0 * self.0 * self.0 }
+///     }
+///     impl seal::Sealed for Square {}
+///     impl seal::Sealed for Circle {}
+///
+///     sealed_eraser!(pub erase_shape, Area, seal::Sealed);
+/// }
+///
+/// use shapes::Area;
+///
+/// fn main() {
+///     let a = shapes::erase_shape(shapes::Square(2.0));
+///     let b = shapes::erase_shape(shapes::Circle(1.0));
+///     assert_eq!(a.area() + b.area(), 7.0);
+/// }
+/// ```
+///
+/// Types without the marker are rejected, even if they implement the trait:
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+///# mod shapes {
+///#     mod seal {
+///#         pub trait Sealed {}
+///#     }
+///#     pub trait Area {
+///#         fn area(&self) -> f64;
+///#     }
+///#     sealed_eraser!(pub erase_shape, Area, seal::Sealed);
+///# }
+/// struct Triangle;
+///
+/// impl shapes::Area for Triangle {
+///     fn area(&self) -> f64 { 0.5 }
+/// }
+///
+///# fn main() {
+/// let c = shapes::erase_shape(Triangle);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! sealed_eraser {
+    // A comma outside any angle brackets ends the trait
+    (@trait [$($head:tt)*] [$($tr:tt)*] [] , $marker:path $(,)?) => {
+        $crate::eraser!($($head)*, $($tr)* where T: $marker);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::sealed_eraser!(@trait [$($head)*] [$($tr)* <] [< $($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::sealed_eraser!(@trait [$($head)*] [$($tr)* >] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::sealed_eraser!(@trait [$($head)*] [$($tr)* >>] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::sealed_eraser!(@trait [$($head)*] [$($tr)* $next] [$($depth)*] $($rest)*);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident, $($rest:tt)*) => {
+        $crate::sealed_eraser!(@trait [$(#[$attr])* $vis $name] [] [] $($rest)*);
+    };
+}
+
+
+/// `let_erased!(name: trait = value)` binds `name` to the value erased to
+/// trait, like `let name = erase!(value, trait);`.
+///
+/// This reads like a `let` with a type annotation, which can't be written for
+/// erased types since they can't be named. The binding may be `mut`, and the
+/// trait may contain `=` inside angle brackets.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let_erased!(total: MyTrait = 10);
+/// assert_eq!(total, 10.into());
+///
+/// let_erased!(mut evens: Iterator<Item = u32> = (1..7).filter(|x| x % 2 == 0));
+/// assert_eq!(evens.next(), Some(2));
+/// assert_eq!(evens.sum::<u32>(), 10);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! let_erased {
+    // An `=` outside any angle brackets ends the trait
+    (@trait [$($head:tt)*] [$($tr:tt)*] [] = $val:expr) => {
+        let $($head)* = $crate::erase!($val, $($tr)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::let_erased!(@trait [$($head)*] [$($tr)* <] [< $($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::let_erased!(@trait [$($head)*] [$($tr)* >] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::let_erased!(@trait [$($head)*] [$($tr)* >>] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::let_erased!(@trait [$($head)*] [$($tr)* $next] [$($depth)*] $($rest)*);
+    };
+    (mut $name:ident : $($rest:tt)*) => {
+        $crate::let_erased!(@trait [mut $name] [] [] $($rest)*);
+    };
+    ($name:ident : $($rest:tt)*) => {
+        $crate::let_erased!(@trait [$name] [] [] $($rest)*);
+    };
+}
+
+
+/// `erased_type!(type Alias = impl trait; fn name(type))` defines a type alias
+/// for an erased type, along with an eraser function which erases values of
+/// the given concrete type to it, when the `nightly-tait` feature is on.
+///
+/// Unlike other erased types, the alias can be named, so it can be the type
+/// of a struct field or an associated type. Every value of the alias has the
+/// one concrete type given for the eraser. This uses type alias impl trait,
+/// which needs a nightly compiler and `#![feature(type_alias_impl_trait)]`
+/// in the crate using the macro. The alias and eraser may each have a
+/// visibility and attributes.
+///
+/// # Examples
+///
+/// ```ignore
+/// #![feature(type_alias_impl_trait)]
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// erased_type!(pub type Erased = impl std::fmt::Debug; pub fn erase(i32));
+///
+/// struct Holder {
+///     value: Erased,
+/// }
+///
+/// fn main() {
+///     let holder = Holder { value: erase(3) };
+///     assert_eq!(format!("{:?}", holder.value), "3");
+/// }
+/// ```
+///
+#[cfg(feature = "nightly-tait")]
+#[macro_export]
+macro_rules! erased_type {
+    (@trait [$($pre:tt)*] $alias:ident [$($tr:tt)*] ; $(#[$attr:meta])* $vis:vis fn $name:ident($ty:ty) $(;)?) => {
+        $($pre)* type $alias = impl $($tr)*;
+
+        #[define_opaque($alias)]
+        $(#[$attr])* $vis fn $name(val: $ty) -> $alias {
+            val
+        }
+    };
+    (@trait [$($pre:tt)*] $alias:ident [$($tr:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erased_type!(@trait [$($pre)*] $alias [$($tr)* $next] $($rest)*);
+    };
+    ($(#[$attr:meta])* $vis:vis type $alias:ident = impl $($rest:tt)*) => {
+        $crate::erased_type!(@trait [$(#[$attr])* $vis] $alias [] $($rest)*);
+    };
+}
+
+
+/// `erase_each!(iterable, trait)` erases every item of anything that can be
+/// iterated over through a single eraser function, producing an iterator
+/// whose items share one anonymous type that is `impl Trait`.
+///
+/// The items are erased lazily, as the iterator is consumed. This is useful
+/// for items which are trait objects, such as `Box<dyn Debug>`, to be handled
+/// as `impl Trait` for a trait the trait object implements. Erasure doesn't
+/// devirtualize them, though: each item is still the boxed trait object, so
+/// every method call still goes through its vtable.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::fmt::Debug;
+///
+/// let boxed: Vec<Box<dyn Debug>> = vec![Box::new(1), Box::new("two")];
+/// let shown: Vec<String> = erase_each!(boxed, Debug)
+///     .map(|item| format!("{:?}", item))
+///     .collect();
+/// assert_eq!(shown, ["1", "\"two\""]);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_each {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            $crate::__private::IntoIterator::into_iter($val).map(__whiteout_erase)
+        }
+    }
+}
+
+
+/// `erase_with_guard!(value, trait, on_drop)` erases a value like
+/// `erase!(value, trait)` and wraps it in a `Guarded`, which runs the
+/// closure `on_drop` once when it is dropped.
+///
+/// The wrapper derefs to the erased value, so the trait's methods can be
+/// called on it directly. It doesn't implement the trait itself, since the
+/// methods of an arbitrary trait can't be forwarded, so it has to be
+/// dereferenced to pass it where the trait is expected.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::cell::Cell;
+///
+/// let released = Cell::new(0);
+/// {
+///     let guarded = erase_with_guard!(vec![1, 2], std::fmt::Debug, || {
+///         released.set(released.get() + 1)
+///     });
+///     assert_eq!(format!("{:?}", *guarded), "[1, 2]");
+///     assert_eq!(released.get(), 0);
+/// }
+/// assert_eq!(released.get(), 1);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_with_guard {
+    // A comma outside any angle brackets ends the trait
+    (@trait [$val:expr] [$($tr:tt)*] [] , $on_drop:expr) => {
+        $crate::erased::Guarded::new($crate::erase!($val, $($tr)*), $on_drop)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::erase_with_guard!(@trait [$val] [$($tr)* <] [< $($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::erase_with_guard!(@trait [$val] [$($tr)* >] [$($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::erase_with_guard!(@trait [$val] [$($tr)* >>] [$($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erase_with_guard!(@trait [$val] [$($tr)* $next] [$($depth)*] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::erase_with_guard!(@trait [$val] [] [] $($rest)*)
+    };
+}
+
+
+/// `erase_sum!(iterable, trait)` erases every item of anything that can be
+/// iterated over through a single eraser function and adds them up with the
+/// trait's `Add`, producing `Some` total that is `impl Trait`, or `None` if
+/// there were no items.
+///
+/// The trait must include `Add<Self, Output = Self>`. Since an arbitrary
+/// trait has no zero to start from, the total starts from the first item.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let values = [1u8, 20, 3].iter().map(|&b| i64::from(b)).chain(vec![-4i64]);
+/// let total = erase_sum!(values, MyTrait);
+/// assert_eq!(total, Some(20.into()));
+///
+/// let none = erase_sum!(Vec::<i64>::new(), MyTrait);
+/// assert!(none.is_none());
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_sum {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            $crate::__private::IntoIterator::into_iter($val)
+                .map(__whiteout_erase)
+                .reduce(|total, item| total + item)
+        }
+    }
+}
+
+
+/// `erase_checked!(value, trait)` erases a value like `erase!`, for use with
+/// `debug_assert_type!`.
+///
+/// Nothing needs to be stored for the check: the compiler still knows the
+/// concrete type behind an erased value, and `debug_assert_type!` asks it for
+/// the name. This macro only marks the values that are meant to be checked.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase_checked!(vec![1u8], std::fmt::Debug);
+/// debug_assert_type!(a, Vec<u8>);
+/// assert_eq!(format!("{:?}", a), "[1]");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_checked {
+    ($val:expr, $($tr:tt)*) => {
+        $crate::erase!($val, $($tr)*)
+    }
+}
+
+
+/// `debug_assert_type!(erased, type)` panics if the concrete type behind an
+/// erased value is not the given type, in builds with debug assertions.
+///
+/// Without debug assertions, such as in release builds, the check compiles
+/// away, and the erased value is not evaluated. Types are compared by name,
+/// so types with the same path, such as from two versions of one crate,
+/// can't be told apart.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// let a = erase_checked!(5u32, std::fmt::Display);
+/// debug_assert_type!(a, u32);
+///
+/// let b = erase_checked!("five", std::fmt::Display);
+/// let wrong = std::panic::catch_unwind(|| debug_assert_type!(b, String));
+/// assert_eq!(wrong.is_err(), cfg!(debug_assertions));
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! debug_assert_type {
+    ($val:expr, $ty:ty) => {
+        debug_assert!(
+            $crate::__private::type_name_of_val(&$val) == $crate::__private::type_name::<$ty>(),
+            "expected an erased value of type `{}`, found `{}`",
+            $crate::__private::type_name::<$ty>(),
+            $crate::__private::type_name_of_val(&$val),
+        )
+    }
+}
+
+
+/// `once_erase!(name, trait, init)` creates a function with the given
+/// identifier which returns a `&'static dyn Trait` to a global value,
+/// initialized by evaluating `init` the first time the function is called.
+///
+/// The value is boxed and kept in a `OnceLock`, so `init` runs at most once,
+/// even if the function is called from several threads at once. Since the
+/// value is shared between threads, the trait must include `Send + Sync`. A
+/// visibility and attributes may be given before the name.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate whiteout;
+///
+/// use std::fmt::Display;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static INITS: AtomicUsize = AtomicUsize::new(0);
+///
+/// once_erase!(greeting, Display + Send + Sync, {
+///     INITS.fetch_add(1, Ordering::SeqCst);
+///     format!("hello {}", "world")
+/// });
+///
+/// fn main() {
+///     assert_eq!(greeting().to_string(), "hello world");
+///     assert_eq!(greeting().to_string(), "hello world");
+///     assert_eq!(INITS.load(Ordering::SeqCst), 1);
+/// }
+/// ```
+///
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! once_erase {
+    // A comma outside any angle brackets ends the trait
+    (@trait [$($head:tt)*] [$($tr:tt)*] [] , $init:expr $(,)?) => {
+        $($head)*() -> &'static $crate::__dyn!($($tr)*) {
+            static __WHITEOUT_VALUE: $crate::__private::OnceLock<$crate::__private::Box<$crate::__dyn!($($tr)*)>> =
+                $crate::__private::OnceLock::new();
+            &**__WHITEOUT_VALUE.get_or_init(|| $crate::__private::Box::new($init))
+        }
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::once_erase!(@trait [$($head)*] [$($tr)* <] [< $($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::once_erase!(@trait [$($head)*] [$($tr)* >] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::once_erase!(@trait [$($head)*] [$($tr)* >>] [$($depth)*] $($rest)*);
+    };
+    (@trait [$($head:tt)*] [$($tr:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::once_erase!(@trait [$($head)*] [$($tr)* $next] [$($depth)*] $($rest)*);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident, $($rest:tt)*) => {
+        $crate::once_erase!(@trait [$(#[$attr])* $vis fn $name] [] [] $($rest)*);
+    };
+}
+
+
+/// `erase_partition!(iterable, trait, predicate)` erases every item of
+/// anything that can be iterated over through a single eraser function, and
+/// splits them into two `Vec`s whose items share one anonymous type that is
+/// `impl Trait`.
+///
+/// The predicate is called with a reference to each item before it is
+/// erased, so it can use the concrete type. Like `Iterator::partition`, the
+/// first `Vec` holds the items for which it returned `true`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# impl<T> MyTrait for T
+///#     where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+///# fn main() {
+/// let (evens, odds) = erase_partition!(1..7, MyTrait, |x| x % 2 == 0);
+/// assert_eq!(evens.len(), 3);
+/// let sum_evens = evens.into_iter().reduce(|a, b| a + b).unwrap();
+/// let sum_odds = odds.into_iter().reduce(|a, b| a + b).unwrap();
+/// assert_eq!(sum_evens, 12.into());
+/// assert_eq!(sum_odds, 9.into());
+///# }
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! erase_partition {
+    // A comma outside any angle brackets ends the trait
+    (@trait [$val:expr] [$($tr:tt)*] [] , $pred:expr) => {
+        {
+            $crate::eraser!(__whiteout_erase, $($tr)*);
+            // Taking the predicate as a generic argument lets it borrow each
+            // item for just the call
+            fn __whiteout_partition<I, P, E, R>(items: I, mut pred: P, erase: E)
+                -> ($crate::__private::Vec<R>, $crate::__private::Vec<R>)
+                where I: Iterator, P: FnMut(&I::Item) -> bool, E: Fn(I::Item) -> R
+            {
+                let mut matching = $crate::__private::Vec::new();
+                let mut rest = $crate::__private::Vec::new();
+                for item in items {
+                    if pred(&item) {
+                        matching.push(erase(item));
+                    } else {
+                        rest.push(erase(item));
+                    }
+                }
+                (matching, rest)
+            }
+            __whiteout_partition(
+                $crate::__private::IntoIterator::into_iter($val),
+                $pred,
+                __whiteout_erase,
+            )
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::erase_partition!(@trait [$val] [$($tr)* <] [< $($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::erase_partition!(@trait [$val] [$($tr)* >] [$($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::erase_partition!(@trait [$val] [$($tr)* >>] [$($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::erase_partition!(@trait [$val] [$($tr)* $next] [$($depth)*] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::erase_partition!(@trait [$val] [] [] $($rest)*)
+    };
+}
+
+
+/// `spawn_erased!(value, trait, closure)` moves a value onto a new thread,
+/// where `closure` is called with it, and returns a
+/// `std::thread::JoinHandle<impl Trait>` for the value the closure returns.
+///
+/// The closure returns a value of the same type as the one it was given, so
+/// joining the handle gives the result erased. Since the value crosses
+/// threads, the trait must include `Send`, and the value must be `'static`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::fmt::Display;
+///
+/// let handle = spawn_erased!(String::from("hello"), Display + Send, |mut s| {
+///     s.push_str(" from a thread");
+///     s
+/// });
+/// assert_eq!(handle.join().unwrap().to_string(), "hello from a thread");
+///# }
+/// ```
+///
+/// Values that can't be sent between threads are rejected.
+///
+/// ```compile_fail,E0277
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::fmt::Debug;
+/// use std::rc::Rc;
+///
+/// let handle = spawn_erased!(Rc::new(1), Debug + Send, |v| v);
+///# }
+/// ```
+///
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! spawn_erased {
+    // A comma outside any angle brackets ends the trait
+    (@trait [$val:expr] [$($tr:tt)*] [] , $f:expr) => {
+        {
+            fn __whiteout_spawn<T, F>(val: T, f: F) -> $crate::__private::JoinHandle<impl $($tr)*>
+                where T: $($tr)* + Send + 'static, F: FnOnce(T) -> T + Send + 'static
+            {
+                $crate::__private::spawn(move || f(val))
+            }
+            __whiteout_spawn($val, $f)
+        }
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::spawn_erased!(@trait [$val] [$($tr)* <] [< $($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::spawn_erased!(@trait [$val] [$($tr)* >] [$($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::spawn_erased!(@trait [$val] [$($tr)* >>] [$($depth)*] $($rest)*)
+    };
+    (@trait [$val:expr] [$($tr:tt)*] [$($depth:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::spawn_erased!(@trait [$val] [$($tr)* $next] [$($depth)*] $($rest)*)
+    };
+    ($val:expr, $($rest:tt)*) => {
+        $crate::spawn_erased!(@trait [$val] [] [] $($rest)*)
+    };
+}
+
+
+/// `erase_deref!(pointer, Target)` erases a smart pointer, such as a `Box`,
+/// an `Rc` or a `String`, into an anonymous type that is
+/// `impl Deref<Target = Target>`.
+///
+/// The target type is kept, so dereferencing the erased pointer still gives
+/// the value it points to, while which kind of pointer holds it is hidden.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::rc::Rc;
+///
+/// let boxed = erase_deref!(Box::new(5), i32);
+/// let counted = erase_deref!(Rc::new(7), i32);
+/// assert_eq!(*boxed + *counted, 12);
+///
+/// let name = erase_deref!(String::from("whiteout"), str);
+/// assert_eq!(name.len(), 8);
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_deref {
+    ($ptr:expr, $target:ty) => {
+        $crate::erase!($ptr, $crate::__private::Deref<Target = $target>)
+    }
+}
+
+
+/// `erase_tagged!(value, trait)` erases a value like `erase!(value, trait)`
+/// and pairs it with the `TypeId` of its type before erasure, giving
+/// `(impl Trait, TypeId)`.
+///
+/// The `TypeId` doesn't reveal the type, but it can be compared, so erased
+/// values can be grouped by the type they came from. As with any `TypeId`,
+/// the value's type must be `'static`.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use]
+///# extern crate whiteout;
+///# fn main() {
+/// use std::any::TypeId;
+///
+/// let (a, a_id) = erase_tagged!(1, std::fmt::Debug);
+/// let (b, b_id) = erase_tagged!("one", std::fmt::Debug);
+/// assert_eq!(a_id, TypeId::of::<i32>());
+/// assert_ne!(a_id, b_id);
+/// assert_eq!(format!("{:?} {:?}", a, b), "1 \"one\"");
+///# }
+/// ```
+///
+#[macro_export]
+macro_rules! erase_tagged {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            let val = $val;
+            let id = $crate::__private::Any::type_id(&val);
+            ($crate::erase!(val, $($tr)*), id)
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! An erased container which only allocates for large values.
+
+use alloc::boxed::Box;
+use core::cell::UnsafeCell;
+use core::mem::{self, MaybeUninit};
+use core::ops::Deref;
+use core::ptr;
+
+// Storage for any function pointer; all of them have the size and alignment
+// of `fn()`
+type FnSlot = MaybeUninit<fn()>;
+
+/// A trait object which is stored inline if its value fits in `N` words and
+/// is no more aligned than a word, and boxed otherwise.
+///
+/// This suits collections of erased values which are mostly small, where
+/// `Box<dyn Trait>` would allocate for every one. It derefs to the trait
+/// object either way, and drops the value when dropped. Use `small_erase!`
+/// to build one from a value.
+///
+/// # Examples
+///
+/// ```
+/// extern crate whiteout;
+///
+/// use std::fmt::Debug;
+/// use whiteout::small::SmallErased;
+///
+/// fn main() {
+///     let values: Vec<SmallErased<dyn Debug, 1>> = vec![
+///         SmallErased::new(1u8, |v| v),
+///         SmallErased::new([0u64; 4], |v| v),
+///     ];
+///     assert!(values[0].is_inline());
+///     assert!(!values[1].is_inline());
+///     assert_eq!(format!("{:?}", &*values[1]), "[0, 0, 0, 0]");
+/// }
+/// ```
+///
+pub struct SmallErased<Tr: ?Sized + 'static, const N: usize> {
+    storage: Storage<N>,
+    // Holds the `fn(&T) -> &Tr` given to `new`, which only `get::<T, Tr>`
+    // reads back, as the same type
+    as_dyn: FnSlot,
+    get: unsafe fn(&FnSlot, *const ()) -> *const Tr,
+    drop: unsafe fn(&mut Storage<N>),
+}
+
+enum Storage<const N: usize> {
+    // The value is reached through `&self`, and may have interior mutability
+    Inline(UnsafeCell<MaybeUninit<[usize; N]>>),
+    Boxed(*mut ()),
+}
+
+impl<const N: usize> Storage<N> {
+    fn as_ptr(&self) -> *const () {
+        match *self {
+            Storage::Inline(ref buf) => buf.get() as *const (),
+            Storage::Boxed(ptr) => ptr,
+        }
+    }
+}
+
+unsafe fn get<T, Tr: ?Sized>(as_dyn: &FnSlot, val: *const ()) -> *const Tr {
+    // Safety: `as_dyn` holds a `fn(&T) -> &Tr` and `val` points to a live `T`
+    let as_dyn = ptr::read(as_dyn.as_ptr() as *const fn(&T) -> &Tr);
+    as_dyn(&*(val as *const T))
+}
+
+unsafe fn drop<T, const N: usize>(storage: &mut Storage<N>) {
+    // Safety: the storage holds a live `T`, which is never used again
+    match *storage {
+        Storage::Inline(ref mut buf) => ptr::drop_in_place(buf.get_mut().as_mut_ptr() as *mut T),
+        Storage::Boxed(ptr) => mem::drop(Box::from_raw(ptr as *mut T)),
+    }
+}
+
+impl<Tr: ?Sized + 'static, const N: usize> SmallErased<Tr, N> {
+    /// Stores a value, along with a function that views it as the trait
+    /// object; for a concrete type, `|v| v` is enough.
+    pub fn new<T: 'static>(val: T, as_dyn: fn(&T) -> &Tr) -> Self {
+        let fits = mem::size_of::<T>() <= mem::size_of::<[usize; N]>()
+            && mem::align_of::<T>() <= mem::align_of::<[usize; N]>();
+        let storage = if fits {
+            let mut buf = MaybeUninit::<[usize; N]>::uninit();
+            // Safety: the buffer is large enough and aligned enough for `T`
+            unsafe { ptr::write(buf.as_mut_ptr() as *mut T, val) };
+            Storage::Inline(UnsafeCell::new(buf))
+        } else {
+            Storage::Boxed(Box::into_raw(Box::new(val)) as *mut ())
+        };
+        let mut slot = FnSlot::uninit();
+        // Safety: the slot is large enough and aligned enough for `as_dyn`
+        unsafe { ptr::write(slot.as_mut_ptr() as *mut fn(&T) -> &Tr, as_dyn) };
+        SmallErased {
+            storage,
+            as_dyn: slot,
+            get: get::<T, Tr>,
+            drop: drop::<T, N>,
+        }
+    }
+
+    /// Returns true if the value is stored inline rather than boxed.
+    pub fn is_inline(&self) -> bool {
+        match self.storage {
+            Storage::Inline(_) => true,
+            Storage::Boxed(_) => false,
+        }
+    }
+}
+
+impl<Tr: ?Sized + 'static, const N: usize> Deref for SmallErased<Tr, N> {
+    type Target = Tr;
+
+    fn deref(&self) -> &Tr {
+        // Safety: `get` and `as_dyn` match the stored value's type
+        unsafe { &*(self.get)(&self.as_dyn, self.storage.as_ptr()) }
+    }
+}
+
+impl<Tr: ?Sized + 'static, const N: usize> Drop for SmallErased<Tr, N> {
+    fn drop(&mut self) {
+        // Safety: `drop` matches the stored value's type
+        unsafe { (self.drop)(&mut self.storage) }
+    }
+}
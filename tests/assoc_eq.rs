@@ -0,0 +1,26 @@
+//! Checks that associated type bindings reach both the bound and the return
+//! type of an eraser.
+
+#[macro_use]
+extern crate whiteout;
+
+eraser!(erase_bytes, Iterator<Item = u8> where T: Clone);
+
+fn sum_bytes(bytes: impl Iterator<Item = u8>) -> u32 {
+    bytes.map(u32::from).sum()
+}
+
+#[test]
+fn items_stay_u8() {
+    let bytes = erase_bytes(vec![1u8, 2, 255].into_iter());
+    let items: Vec<u8> = bytes.collect();
+    assert_eq!(items, [1, 2, 255]);
+    assert_eq!(sum_bytes(erase_bytes(b"ab".iter().cloned())), 97 + 98);
+}
+
+#[test]
+fn where_clause_binding() {
+    eraser!(erase_chars, Iterator<Item = char> where T: DoubleEndedIterator<Item = char>);
+    let reversed: String = erase_chars("abc".chars().rev()).collect();
+    assert_eq!(reversed, "cba");
+}
@@ -0,0 +1,60 @@
+//! Checks that the `bloat` registry records each erasure site once for each
+//! type it's called with; run with `--features bloat-report`.
+
+#![cfg(feature = "bloat-report")]
+
+#[macro_use]
+extern crate whiteout;
+
+use whiteout::bloat;
+
+eraser!(erase_debug, std::fmt::Debug);
+
+#[test]
+fn registers_distinct_sites() {
+    for i in 0..3 {
+        let _ = format!("{:?}", erase_debug(i));
+    }
+    let _ = format!("{:?}", erase_debug("three"));
+    let a = erase!(1u8, std::fmt::Display);
+    let b = erase!(2u16, std::fmt::Display);
+    let _ = format!("{} {}", a, b);
+
+    let sites: Vec<_> = bloat::sites().into_iter()
+        .filter(|s| s.module == module_path!())
+        .collect();
+    assert_eq!(sites.len(), 4);
+    let debug: Vec<_> = sites.iter().filter(|s| s.trait_name == "std::fmt::Debug").map(|s| s.type_name).collect();
+    assert_eq!(debug, ["i32", "&str"]);
+    assert_eq!(sites.iter().filter(|s| s.trait_name == "std::fmt::Display").count(), 2);
+    assert!(sites.iter().all(|s| s.file.ends_with("bloat.rs")));
+    assert!(bloat::report().contains("erases u16 to std::fmt::Display"));
+}
+
+#[test]
+fn const_erasers_are_not_registered() {
+    let line = line!() + 1;
+    const_eraser!(const_erase_debug, std::fmt::Debug);
+    let _ = format!("{:?}", const_erase_debug(1));
+    assert!(bloat::sites().iter().all(|s| s.line != line));
+}
+
+// In its own module, so its sites are told apart from the tests above
+mod alternating {
+    use whiteout::bloat;
+
+    eraser!(erase_alternating, std::fmt::Debug);
+
+    #[test]
+    fn alternating_types_are_each_registered() {
+        for i in 0..3 {
+            let _ = format!("{:?}", erase_alternating(i));
+            let _ = format!("{:?}", erase_alternating(i as u8));
+        }
+        let types: Vec<_> = bloat::sites().into_iter()
+            .filter(|s| s.module == module_path!())
+            .map(|s| s.type_name)
+            .collect();
+        assert_eq!(types, ["i32", "u8"]);
+    }
+}
@@ -0,0 +1,26 @@
+//! Checks that `debug_assert_type!` panics on a wrong type only when debug
+//! assertions are on.
+
+#[macro_use]
+extern crate whiteout;
+
+#[test]
+fn right_type_passes() {
+    let a = erase_checked!(vec![1u8, 2], std::fmt::Debug);
+    debug_assert_type!(a, Vec<u8>);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "expected an erased value of type `u64`, found `u32`")]
+fn wrong_type_panics() {
+    let a = erase_checked!(1u32, std::fmt::Debug);
+    debug_assert_type!(a, u64);
+}
+
+#[cfg(not(debug_assertions))]
+#[test]
+fn wrong_type_is_not_checked() {
+    let a = erase_checked!(1u32, std::fmt::Debug);
+    debug_assert_type!(a, u64);
+}
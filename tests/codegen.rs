@@ -0,0 +1,105 @@
+//! Checks that generated erasers are valid Rust which works like `eraser!`,
+//! by including the ones the build script writes; run with
+//! `--features codegen`.
+
+#![cfg(feature = "codegen")]
+
+extern crate whiteout;
+
+use whiteout::codegen::{eraser_source, erasure_sites, Visibility, ERASING_MACROS};
+
+// The erasers the build script generated with `eraser_source`, the same
+// calls as the tests below make
+include!(concat!(env!("OUT_DIR"), "/codegen_erasers.rs"));
+
+#[test]
+fn public_eraser() {
+    assert_eq!(
+        eraser_source("erase_debug", "std::fmt::Debug", Visibility::Public),
+        "pub fn erase_debug<T: std::fmt::Debug>(val: T) -> impl std::fmt::Debug {\n    val\n}\n"
+    );
+    assert_eq!(format!("{:?}", erase_debug(1)), "1");
+}
+
+#[test]
+fn crate_eraser() {
+    assert_eq!(
+        eraser_source("erase_add", "std::ops::Add<i64, Output=i64>", Visibility::Crate),
+        "pub(crate) fn erase_add<T: std::ops::Add<i64, Output=i64>>(val: T) \
+         -> impl std::ops::Add<i64, Output=i64> {\n    val\n}\n"
+    );
+    assert_eq!(erase_add(1) + 2, 3);
+}
+
+#[test]
+fn private_eraser() {
+    assert_eq!(
+        eraser_source("erase_clone", "Clone", Visibility::Private),
+        "fn erase_clone<T: Clone>(val: T) -> impl Clone {\n    val\n}\n"
+    );
+    let a = erase_clone(vec![1]);
+    let _ = (a.clone(), a);
+}
+
+// The exported macros which don't make a new anonymous type for each call;
+// every other one must be in `ERASING_MACROS`.
+const NON_ERASING_MACROS: &[&str] = &[
+    "arc_erase", "assert_erasable", "boxerase", "clone_erase", "cow_erase",
+    "debug_assert_type", "deref_erase", "erase_boxed_fn", "erase_err",
+    "erase_into_boxed_slice", "erase_iter_boxed",
+    "erase_serialize", "match_box_erase", "once_erase", "pin_erase", "push_erased",
+    "rc_erase", "refcell_erase", "replace_erase", "small_erase", "unerase",
+    "upcast_erase", "weak_erase",
+];
+
+#[test]
+fn every_macro_is_classified() {
+    let source = include_str!("../src/lib.rs");
+    let mut names: Vec<&str> = source.lines()
+        .filter_map(|l| l.strip_prefix("macro_rules! "))
+        .map(|l| l.trim_end_matches(" {"))
+        .filter(|name| !name.starts_with("__"))
+        .collect();
+    names.dedup();
+    for name in names {
+        let erasing = ERASING_MACROS.contains(&name);
+        let non_erasing = NON_ERASING_MACROS.contains(&name);
+        assert!(erasing != non_erasing, "`{}!` must be in exactly one list", name);
+    }
+}
+
+#[test]
+fn sites_in_source() {
+    let source = "
+        eraser!(erase_debug, Debug);
+        /* tuple_erase!((1, 2), Debug) */
+        let a = erase_debug(1);
+        let b = erase!('\"', Debug);
+        let c = whiteout::erase_ref! (&a, Debug);
+        let d = boxerase!(4, Debug);
+        if erase != 1 {}
+    ";
+    let sites: Vec<_> = erasure_sites(source).into_iter().map(|s| (s.macro_name, s.line)).collect();
+    assert_eq!(sites, [("eraser", 2), ("erase", 5), ("erase_ref", 6)]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_report_to_out_dir() {
+    use whiteout::codegen::write_bloat_report;
+
+    let dir = std::env::temp_dir().join(format!("whiteout-bloat-{}", std::process::id()));
+    let src = dir.join("src");
+    std::fs::create_dir_all(src.join("nested")).unwrap();
+    std::fs::write(src.join("lib.rs"), "fn f() { erase!(1, Debug); erase!(2, Debug); }\n").unwrap();
+    std::fs::write(src.join("nested").join("mod.rs"), "\neraser!(e, Clone);\n").unwrap();
+    std::fs::write(src.join("notes.txt"), "erase!(3, Debug)").unwrap();
+
+    assert_eq!(write_bloat_report(&src, &dir).unwrap(), 3);
+    let report = std::fs::read_to_string(dir.join("whiteout-bloat.txt")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], "whiteout: 3 erasure sites");
+    assert!(lines[1].ends_with("lib.rs:1 erase!"));
+    assert!(lines[3].ends_with("mod.rs:2 eraser!"));
+}
@@ -0,0 +1,26 @@
+//! Checks that `erase_deref!` keeps the target of the erased pointer.
+
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+extern crate whiteout;
+
+use std::ops::Deref;
+
+fn read(p: &impl Deref<Target = i32>) -> i32 {
+    **p
+}
+
+#[test]
+fn deref_erased_box() {
+    let erased = erase_deref!(Box::new(41), i32);
+    assert_eq!(*erased + 1, 42);
+    assert_eq!(read(&erased), 41);
+}
+
+#[test]
+fn deref_erased_slice() {
+    let erased = erase_deref!(vec![1u8, 2, 3], [u8]);
+    assert_eq!(erased.iter().sum::<u8>(), 6);
+    assert_eq!(&*erased, [1, 2, 3]);
+}
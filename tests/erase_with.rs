@@ -0,0 +1,23 @@
+//! Checks that `Erase::erase_with` takes borrowed values too.
+
+#[macro_use]
+extern crate whiteout;
+
+use whiteout::Erase;
+
+eraser!(erase_debug, std::fmt::Debug);
+eraser!(erase_bytes<'a>, Iterator<Item = &'a u8> + 'a);
+
+#[test]
+fn borrowed_value() {
+    let s = String::from("borrowed");
+    let a = (&s).erase_with(erase_debug);
+    assert_eq!(format!("{:?}", a), "\"borrowed\"");
+}
+
+#[test]
+fn borrowing_iterator() {
+    let v = [1u8, 2, 3];
+    let total: u8 = v.iter().erase_with(erase_bytes).sum();
+    assert_eq!(total, 6);
+}
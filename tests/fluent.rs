@@ -0,0 +1,48 @@
+//! Checks that trait methods returning `Self` return the erased type, so
+//! fluent chains keep working on erased values.
+
+#[macro_use]
+extern crate whiteout;
+
+trait Scale: Sized {
+    fn doubled(self) -> Self;
+    fn halved(&self) -> Self;
+    fn value(&self) -> i64;
+}
+
+impl Scale for i64 {
+    fn doubled(self) -> Self {
+        self * 2
+    }
+
+    fn halved(&self) -> Self {
+        self / 2
+    }
+
+    fn value(&self) -> i64 {
+        *self
+    }
+}
+
+eraser!(erase_scale, Scale);
+
+#[test]
+fn chain_by_value() {
+    let a = erase!(3i64, Scale);
+    assert_eq!(a.doubled().doubled().value(), 12);
+}
+
+#[test]
+fn chain_by_reference() {
+    let a = erase_scale(40i64);
+    assert_eq!(a.halved().halved().value(), 10);
+}
+
+#[test]
+fn returned_self_is_the_erased_type() {
+    let mut a = erase_scale(5i64);
+    // Assigning back only compiles if `doubled` returns the same type
+    a = a.doubled();
+    a = erase_scale(a.value() + 1);
+    assert_eq!(a.value(), 11);
+}
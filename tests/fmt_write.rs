@@ -0,0 +1,42 @@
+//! Checks that `erase_fmt_write!` lets one formatting routine write to
+//! different sinks.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::fmt::{self, Write};
+
+/// A sink that only counts what is written to it.
+#[derive(Default)]
+struct Counter {
+    chars: usize,
+    writes: usize,
+}
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.chars += s.chars().count();
+        self.writes += 1;
+        Ok(())
+    }
+}
+
+fn render(out: &mut impl Write, name: &str, count: u32) -> fmt::Result {
+    write!(out, "{}: {:>3}", name, count)
+}
+
+#[test]
+fn write_to_string() {
+    let mut s = String::new();
+    render(erase_fmt_write!(&mut s), "apples", 7).unwrap();
+    assert_eq!(s, "apples:   7");
+}
+
+#[test]
+fn write_to_custom_sink() {
+    let mut counter = Counter::default();
+    render(erase_fmt_write!(&mut counter), "pears", 12).unwrap();
+    writeln!(erase_fmt_write!(&mut counter)).unwrap();
+    assert_eq!(counter.chars, "pears:  12\n".len());
+    assert!(counter.writes > 1);
+}
@@ -0,0 +1,83 @@
+//! Checks that the closure given to `erase_with_guard!` runs exactly once,
+//! after the erased value is dropped.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+struct Tracked {
+    events: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Describe for Tracked {
+    fn describe(&self) -> String {
+        "tracked".to_string()
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.events.borrow_mut().push("value");
+    }
+}
+
+#[test]
+fn guard_runs_once_after_value() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let guarded = {
+        let events = events.clone();
+        erase_with_guard!(Tracked { events: events.clone() }, Describe, move || {
+            events.borrow_mut().push("guard")
+        })
+    };
+    assert_eq!(guarded.describe(), "tracked");
+    assert!(events.borrow().is_empty());
+    drop(guarded);
+    assert_eq!(*events.borrow(), ["value", "guard"]);
+}
+
+#[test]
+fn trait_with_commas() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    {
+        let events = events.clone();
+        let guarded = erase_with_guard!(vec![1, 2], std::ops::Index<usize, Output = i32>, move || {
+            events.borrow_mut().push("guard")
+        });
+        assert_eq!(guarded[1], 2);
+    }
+    assert_eq!(*events.borrow(), ["guard"]);
+}
+
+struct Explodes;
+
+impl Describe for Explodes {
+    fn describe(&self) -> String {
+        "explodes".to_string()
+    }
+}
+
+impl Drop for Explodes {
+    fn drop(&mut self) {
+        panic!("destructor panicked");
+    }
+}
+
+#[test]
+fn guard_runs_when_destructor_panics() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let guarded = {
+        let events = events.clone();
+        erase_with_guard!(Explodes, Describe, move || events.borrow_mut().push("guard"))
+    };
+    assert_eq!(guarded.describe(), "explodes");
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(guarded)));
+    assert!(result.is_err());
+    assert_eq!(*events.borrow(), ["guard"]);
+}
@@ -0,0 +1,64 @@
+//! Checks that the helpers the macros define don't shadow the caller's names.
+
+#[macro_use]
+extern crate whiteout;
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+struct Holder(i32);
+
+fn f() -> i32 {
+    1
+}
+
+fn ok() -> Result<i32, i32> {
+    Ok(2)
+}
+
+fn err() -> Result<i32, i32> {
+    Err(3)
+}
+
+#[test]
+fn erase_calls_callers_f() {
+    let a = erase!(f(), std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "1");
+}
+
+#[test]
+fn local_f_is_untouched() {
+    let f = |x: i32| x * 10;
+    let a = erase!(f(2), std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "20");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn collections_call_callers_f() {
+    let a = erase_all!([f(), f() + 1], std::fmt::Debug);
+    let b = tuple_erase!((f(), "x"), std::fmt::Debug);
+    let c = erase_vec!(vec![f()], std::fmt::Debug);
+    assert_eq!(format!("{:?} {:?} {:?}", a, b, c), "[1, 2] (1, \"x\") [1]");
+}
+
+#[test]
+fn erase_result_calls_callers_ok_and_err() {
+    let a = erase_result!(ok(), std::fmt::Debug);
+    let b = erase_result!(err(), std::fmt::Debug);
+    assert_eq!(format!("{:?} {:?}", a, b), "Ok(2) Err(3)");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn clone_erase_uses_callers_holder() {
+    let holder = Holder(f());
+    assert_eq!(holder.0, 1);
+    let a = clone_erase!(holder, std::fmt::Debug);
+    assert_eq!(format!("{:?}", &*a.clone()), "Holder(1)");
+}
+
+#[test]
+fn scoped_eraser_calls_callers_f() {
+    let a = scoped_eraser!(std::fmt::Debug, |e| e(f()));
+    assert_eq!(format!("{:?}", a), "1");
+}
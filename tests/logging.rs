@@ -0,0 +1,38 @@
+//! Checks that `erase_logged!` logs the type name of each erased value when
+//! the `logging` feature is on.
+
+#![cfg(feature = "logging")]
+
+#[macro_use]
+extern crate whiteout;
+
+use std::cell::RefCell;
+
+thread_local! {
+    static LINES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+// Stands in for the `log` crate, recording each message instead
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        ::LINES.with(|lines| lines.borrow_mut().push(format!($($arg)*)))
+    };
+}
+
+mod log {
+    pub use trace;
+}
+
+#[test]
+fn logs_type_name() {
+    let a = erase_logged!(vec![1u8, 2], std::fmt::Debug);
+    let b = erase_logged!("text", std::fmt::Display);
+    assert_eq!(format!("{:?} {}", a, b), "[1, 2] text");
+    LINES.with(|lines| {
+        assert_eq!(*lines.borrow(), [
+            "erasing a value of type alloc::vec::Vec<u8>",
+            "erasing a value of type &str",
+        ]);
+    });
+}
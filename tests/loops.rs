@@ -0,0 +1,22 @@
+//! Checks that values erased in a loop by one `loop_eraser!` share a type.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::ops::Add;
+
+trait Amount: Add<Output = Self> + Copy + Into<u64> {}
+
+impl<T: Add<Output = T> + Copy + Into<u64>> Amount for T {}
+
+#[test]
+fn sum_values_erased_in_loop() {
+    let e = loop_eraser!(Amount);
+    let mut values = Vec::new();
+    for i in 1..=4u32 {
+        values.push(e(i * 10));
+    }
+    let total = values.iter().fold(e(0), |acc, &v| acc + v);
+    assert_eq!(total.into(), 100u64);
+    assert_eq!(values.len(), 4);
+}
@@ -0,0 +1,27 @@
+//! Checks that `match_box_erase!` boxes arms of different types as one trait
+//! object.
+
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+extern crate whiteout;
+
+use std::fmt::Debug;
+
+enum Payload {
+    Number(i32),
+    Name(String),
+}
+
+fn erase_payload(p: Payload) -> Box<dyn Debug> {
+    match_box_erase!(p, Debug, {
+        Payload::Number(n) => n,
+        Payload::Name(s) => s,
+    })
+}
+
+#[test]
+fn format_boxed_arms() {
+    let erased = vec![erase_payload(Payload::Number(-4)), erase_payload(Payload::Name("four".to_string()))];
+    assert_eq!(format!("{:?}", erased), "[-4, \"four\"]");
+}
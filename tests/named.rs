@@ -0,0 +1,45 @@
+//! Checks that values wrapped by `erase_named!` can be stored in struct
+//! fields and still have the trait's methods.
+
+#[macro_use]
+extern crate whiteout;
+
+trait Counter {
+    fn count(&self) -> u32;
+    fn bump(&mut self);
+}
+
+impl Counter for u32 {
+    fn count(&self) -> u32 { *self }
+    fn bump(&mut self) { *self += 1; }
+}
+
+erase_named!(
+    /// A counter of some erased type.
+    pub struct NamedCounter, Counter
+);
+
+struct Tally<T> {
+    counter: NamedCounter<T>,
+    label: &'static str,
+}
+
+fn start(at: u32) -> NamedCounter<impl Counter> {
+    erase_named!(NamedCounter, at, Counter)
+}
+
+#[test]
+fn stored_in_field() {
+    let mut tally = Tally { counter: start(1), label: "clicks" };
+    tally.counter.bump();
+    tally.counter.bump();
+    assert_eq!(tally.counter.count(), 3);
+    assert_eq!(tally.label, "clicks");
+}
+
+#[test]
+fn non_object_safe_trait() {
+    erase_named!(struct Same, PartialEq + Clone);
+    let a = erase_named!(Same, 5u8, PartialEq + Clone);
+    assert!(*a == a.clone());
+}
@@ -0,0 +1,44 @@
+//! Checks that the macros expand without anything from `std` in scope.
+
+#![no_std]
+
+#[macro_use]
+extern crate whiteout;
+
+use core::ops::Add;
+
+eraser!(erase_add, Add<i64, Output = i64>);
+
+#[test]
+fn erase_works() {
+    let a = erase!(10, Add<i64, Output = i64>);
+    assert_eq!(a + 10, 20);
+}
+
+#[test]
+fn eraser_works() {
+    let a = erase_add(10);
+    let b = erase_add(5);
+    assert_eq!(a + 1, 11);
+    assert_eq!(b + 1, 6);
+}
+
+#[test]
+fn references_work() {
+    let mut x = 5i64;
+    assert!(*erase_ref!(&x, PartialEq<i64>) == 5);
+    assert!(*erase_mut!(&mut x, PartialEq<i64>) == 5);
+}
+
+#[test]
+fn unerase_works() {
+    let a = erase!(10i32, core::any::Any);
+    assert_eq!(unerase!(a, i32), Some(&10));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn boxerase_works() {
+    let a = boxerase!(10i64, PartialEq<i64>);
+    assert!(*a == 10);
+}
@@ -0,0 +1,81 @@
+//! Checks that parsers of different types, with one `Output` and `Error`,
+//! can be erased to one trait object type and tried in sequence.
+
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+extern crate whiteout;
+
+trait Parser {
+    type Output;
+    type Error;
+
+    fn parse(&self, input: &str) -> Result<Self::Output, Self::Error>;
+}
+
+struct Decimal;
+
+struct Hex {
+    prefix: &'static str,
+}
+
+impl Parser for Decimal {
+    type Output = i64;
+    type Error = String;
+
+    fn parse(&self, input: &str) -> Result<i64, String> {
+        input.parse().map_err(|_| format!("{:?} is not decimal", input))
+    }
+}
+
+impl Parser for Hex {
+    type Output = i64;
+    type Error = String;
+
+    fn parse(&self, input: &str) -> Result<i64, String> {
+        let digits = input.strip_prefix(self.prefix).ok_or_else(|| format!("{:?} has no prefix", input))?;
+        i64::from_str_radix(digits, 16).map_err(|_| format!("{:?} is not hex", input))
+    }
+}
+
+fn parsers() -> Vec<Box<dyn Parser<Output = i64, Error = String>>> {
+    vec![
+        boxerase!(Decimal, Parser<Output = i64, Error = String>),
+        boxerase!(Hex { prefix: "0x" }, Parser<Output = i64, Error = String>),
+    ]
+}
+
+fn parse_any(input: &str) -> Result<i64, Vec<String>> {
+    let mut errors = Vec::new();
+    for parser in parsers() {
+        match parser.parse(input) {
+            Ok(value) => return Ok(value),
+            Err(err) => errors.push(err),
+        }
+    }
+    Err(errors)
+}
+
+#[test]
+fn first_parser_wins() {
+    assert_eq!(parse_any("42"), Ok(42));
+}
+
+#[test]
+fn falls_through_to_later_parser() {
+    assert_eq!(parse_any("0xff"), Ok(255));
+}
+
+#[test]
+fn collects_every_error() {
+    assert_eq!(parse_any("zz"), Err(vec![
+        "\"zz\" is not decimal".to_string(),
+        "\"zz\" has no prefix".to_string(),
+    ]));
+}
+
+#[test]
+fn run_every_parser() {
+    let results: Vec<Result<i64, String>> = parsers().iter().map(|p| p.parse("10")).collect();
+    assert_eq!(results, [Ok(10), Err("\"10\" has no prefix".to_string())]);
+}
@@ -0,0 +1,584 @@
+//! Checks that the macros work when imported by path, without
+//! `#[macro_use]`, so that every helper they call is found through `$crate`.
+
+#![cfg_attr(feature = "nightly-tait", feature(type_alias_impl_trait))]
+
+#[cfg(feature = "serde")]
+extern crate erased_serde;
+extern crate whiteout;
+
+use whiteout::{
+    chainable_eraser,
+    const_erase,
+    const_eraser,
+    define_eraser_trait,
+    erase,
+    erase_add,
+    erase_all,
+    erase_borrow,
+    erase_checked,
+    erase_deref,
+    erase_each,
+    erase_enum,
+    erase_fields,
+    erase_fmt_write,
+    erase_from_fn,
+    erase_if,
+    erase_into_iter,
+    erase_iter,
+    erase_logged,
+    erase_manual,
+    erase_mut,
+    erase_named,
+    erase_ok,
+    erase_opt,
+    erase_pair,
+    erase_ref,
+    erase_repeat,
+    erase_result,
+    erase_sum,
+    erase_tagged,
+    erase_toggle,
+    erase_with_guard,
+    erase_with_type_name,
+    eraser,
+    eraser_for_each,
+    eraser_method,
+    eraser_with_default,
+    erasers,
+    from_erase,
+    layout_erase,
+    let_erased,
+    loop_eraser,
+    reerase,
+    scoped_eraser,
+    sealed_erase,
+    sealed_eraser,
+    tuple_erase,
+};
+#[cfg(feature = "std")]
+use whiteout::{erase_map_keys, once_erase, spawn_erased};
+#[cfg(feature = "alloc")]
+use whiteout::{
+    arc_erase,
+    boxerase,
+    clone_erase,
+    cow_erase,
+    deref_erase,
+    erase_boxed_fn,
+    erase_collect,
+    erase_dyn_iter,
+    erase_err,
+    erase_into_boxed_slice,
+    erase_iter_boxed,
+    erase_partition,
+    match_box_erase,
+    pin_erase,
+    push_erased,
+    rc_erase,
+    refcell_erase,
+    small_erase,
+    upcast_erase,
+    weak_erase,
+};
+#[cfg(feature = "serde")]
+use whiteout::erase_serialize;
+
+// Stands in for the `log` crate, which `erase_logged!` calls with the
+// `logging` feature on
+#[cfg(feature = "logging")]
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => { let _ = format!($($arg)*); };
+}
+
+#[cfg(feature = "logging")]
+mod log {
+    pub use trace;
+}
+
+eraser!(erase_debug, std::fmt::Debug);
+
+#[test]
+fn eraser_imported_by_path() {
+    assert_eq!(format!("{:?}", erase_debug([1, 2])), "[1, 2]");
+}
+
+#[test]
+fn erase_with_type_name_imported_by_path() {
+    let a = erase_with_type_name!(3u8, std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "3");
+}
+
+#[test]
+fn erase_logged_imported_by_path() {
+    let a = erase_logged!("four", std::fmt::Display);
+    assert_eq!(a.to_string(), "four");
+}
+
+#[test]
+fn erase_ref_imported_by_path() {
+    let s = String::from("five");
+    let a = erase_ref!(&s, std::fmt::Display);
+    assert_eq!(a.to_string(), "five");
+}
+
+#[test]
+fn erase_iter_imported_by_path() {
+    let a = erase_iter!((1u8..4).map(|x| x * 2), u8);
+    assert_eq!(a.collect::<Vec<u8>>(), [2, 4, 6]);
+}
+
+#[test]
+fn erase_all_imported_by_path() {
+    let [a, b] = erase_all!([1, 2], std::ops::Add<Output = i32>);
+    assert_eq!(a + b, 3);
+}
+
+#[test]
+fn erase_mut_imported_by_path() {
+    let mut v = vec![1u8];
+    erase_mut!(&mut v, Extend<u8>).extend([2, 3].iter().cloned());
+    assert_eq!(v, [1, 2, 3]);
+}
+
+#[test]
+fn erase_borrow_imported_by_path() {
+    let s = String::from("six");
+    let r = &s;
+    assert_eq!(erase_borrow!(*r, std::fmt::Display).to_string(), "six");
+}
+
+#[test]
+fn erase_fmt_write_imported_by_path() {
+    use std::fmt::Write;
+
+    let mut s = String::new();
+    write!(erase_fmt_write!(&mut s), "{}", 7).unwrap();
+    assert_eq!(s, "7");
+}
+
+const_eraser!(erase_const_debug, Copy + std::fmt::Debug);
+
+#[test]
+fn const_erasers_imported_by_path() {
+    let a = const { erase_const_debug(8) };
+    let b = const { const_erase!(9, Copy + std::fmt::Debug) };
+    assert_eq!(format!("{:?} {:?}", a, b), "8 9");
+}
+
+define_eraser_trait!(Summable: std::ops::Add<Self, Output = Self> + PartialEq + From<u8>; erase_summable);
+
+#[test]
+fn define_eraser_trait_imported_by_path() {
+    assert!(erase_summable(2u16) + erase_summable(3u16) == 5.into());
+}
+
+chainable_eraser!(erase_chained, std::ops::Add<Output = Self> + PartialEq);
+
+#[test]
+fn chainable_eraser_imported_by_path() {
+    assert!((erase_chained(1) + erase_chained(2)) + erase_chained(3) == erase_chained(6));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn spawn_erased_imported_by_path() {
+    let handle = spawn_erased!(vec![1u8], std::fmt::Debug + Send, |mut v| {
+        v.push(2);
+        v
+    });
+    assert_eq!(format!("{:?}", handle.join().unwrap()), "[1, 2]");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn weak_erase_imported_by_path() {
+    let (_rc, weak) = weak_erase!(Rc, 1, std::fmt::Display);
+    let (_arc, weak_sync) = weak_erase!(Arc, 2, std::fmt::Display + Send + Sync);
+    assert_eq!(weak.upgrade().unwrap().to_string(), "1");
+    assert_eq!(weak_sync.upgrade().unwrap().to_string(), "2");
+}
+
+#[test]
+fn erase_pair_imported_by_path() {
+    let (a, b) = erase_pair!(1, 2, std::ops::Add<Output = i32> + PartialOrd);
+    assert!(a < b);
+    assert_eq!(a + b, 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn erase_boxed_fn_imported_by_path() {
+    let double = erase_boxed_fn!(|x| x * 2, Fn(i32) -> i32);
+    assert_eq!(double(4), 8);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn erase_serialize_imported_by_path() {
+    let _: Box<dyn erased_serde::Serialize + Send> = erase_serialize!(1u8, Send);
+}
+
+#[test]
+fn erase_deref_imported_by_path() {
+    let five = 5;
+    assert_eq!(*erase_deref!(&five, i32) + 1, 6);
+}
+
+#[test]
+fn erase_tagged_imported_by_path() {
+    let (a, id) = erase_tagged!(2u8, std::fmt::Debug);
+    assert_eq!(id, std::any::TypeId::of::<u8>());
+    assert_eq!(format!("{:?}", a), "2");
+}
+
+trait Summed: std::ops::Add<Self, Output = Self> + Into<i64> {}
+
+impl<T: std::ops::Add<Output = T> + Into<i64>> Summed for T {}
+
+#[test]
+fn erase_sum_imported_by_path() {
+    let total = erase_sum!(vec![1, 2, 3], Summed);
+    assert_eq!(total.map(Into::into), Some(6i64));
+}
+
+#[test]
+fn erase_checked_imported_by_path() {
+    let a = erase_checked!(3u8, std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "3");
+}
+
+#[test]
+fn erase_result_imported_by_path() {
+    let r: Result<u8, &str> = Err("no");
+    let erased = erase_result!(r, std::fmt::Debug);
+    assert_eq!(format!("{:?}", erased), "Err(\"no\")");
+}
+
+#[test]
+fn erase_ok_imported_by_path() {
+    let r: Result<u8, ()> = Ok(4);
+    assert_eq!(erase_ok!(r, std::fmt::Display).unwrap().to_string(), "4");
+}
+
+#[test]
+fn erase_opt_imported_by_path() {
+    let a = erase_opt!(Some(5), std::fmt::Display);
+    assert_eq!(a.map(|v| v.to_string()), Some("5".to_string()));
+}
+
+#[test]
+fn erase_if_imported_by_path() {
+    let a = erase_if!(all(), 5, std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "5");
+}
+
+erasers! {
+    erase_listed_debug => std::fmt::Debug,
+    pub erase_listed_display => std::fmt::Display,
+}
+
+#[test]
+fn erasers_imported_by_path() {
+    assert_eq!(format!("{:?} {}", erase_listed_debug(1), erase_listed_display(2)), "1 2");
+}
+
+#[test]
+fn scoped_eraser_imported_by_path() {
+    let sum = scoped_eraser!(std::ops::Add<Output = i32>, |e| e(1) + e(2));
+    assert_eq!(sum, 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn erase_err_imported_by_path() {
+    let r: Result<i32, Box<dyn std::fmt::Debug>> = erase_err!("x".parse::<i32>(), std::fmt::Debug);
+    assert!(r.is_err());
+}
+
+#[test]
+fn erase_into_iter_imported_by_path() {
+    let bytes = erase_into_iter!(vec![1u8, 2], u8);
+    assert_eq!(bytes.into_iter().sum::<u8>(), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn erase_iter_boxed_imported_by_path() {
+    let boxed = erase_iter_boxed!((1..3).map(|x| x * 3), i32);
+    assert_eq!(boxed.collect::<Vec<i32>>(), [3, 6]);
+}
+
+#[test]
+fn erase_from_fn_imported_by_path() {
+    let mut left = 2;
+    let countdown = erase_from_fn!(move || {
+        left -= 1;
+        if left >= 0 { Some(left) } else { None }
+    }, i32);
+    assert_eq!(countdown.collect::<Vec<i32>>(), [1, 0]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn erase_dyn_iter_imported_by_path() {
+    let items = erase_dyn_iter!(vec![1, 2], std::fmt::Debug);
+    let shown: Vec<String> = items.map(|item| format!("{:?}", item)).collect();
+    assert_eq!(shown, ["1", "2"]);
+}
+
+#[test]
+fn reerase_imported_by_path() {
+    let a = reerase!(vec![1u8], std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "[1]");
+}
+
+struct Methods;
+
+eraser_method!(Methods, erase_debug, std::fmt::Debug);
+
+#[test]
+fn eraser_method_imported_by_path() {
+    assert_eq!(format!("{:?}", Methods::erase_debug(5)), "5");
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn erase_fields_imported_by_path() {
+    let (x, y) = erase_fields!(Point { x: 1, y: 2 }, std::ops::Add<Output = i32>, [x, y]);
+    assert_eq!(x + y, 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn deref_erase_imported_by_path() {
+    let a = deref_erase!(10, std::fmt::Display);
+    assert_eq!(a.to_string(), "10");
+}
+
+#[test]
+fn from_erase_imported_by_path() {
+    let a = from_erase!(1u8, i64, std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "1");
+}
+
+#[test]
+fn erase_manual_imported_by_path() {
+    let handle = erase_manual!(String::from("held"), std::fmt::Display);
+    assert_eq!(handle.to_string(), "held");
+    whiteout::ffi::drop_erased(handle);
+}
+
+enum Number {
+    Int(i32),
+    Byte(u8),
+}
+
+#[test]
+fn erase_enum_imported_by_path() {
+    let erase_number = |n| erase_enum!(n, std::ops::Add<Output = i32>, Number {
+        Int(x) => x,
+        Byte(y) => i32::from(y),
+    });
+    assert_eq!(erase_number(Number::Int(10)) + erase_number(Number::Byte(5)), 15);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn erase_collect_imported_by_path() {
+    let items = erase_collect!(1..3, std::fmt::Debug => Vec<_>);
+    assert_eq!(format!("{:?}", items), "[1, 2]");
+}
+
+eraser_for_each!(std::fmt::Debug => [erase_first, pub erase_second]);
+
+#[test]
+fn eraser_for_each_imported_by_path() {
+    assert_eq!(format!("{:?} {:?}", erase_first(1), erase_second(2)), "1 2");
+}
+
+#[test]
+fn layout_erase_imported_by_path() {
+    let a = layout_erase!(7u16, std::fmt::Debug);
+    assert_eq!(a.layout().size(), 2);
+    assert_eq!(format!("{:?}", *a), "7");
+}
+
+#[test]
+fn erase_repeat_imported_by_path() {
+    let [a, b] = erase_repeat!(3, std::ops::Add<Output = i32> + Clone; 2);
+    assert_eq!(a + b, 6);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn push_erased_imported_by_path() {
+    let mut shown: Vec<Box<dyn std::fmt::Display>> = Vec::new();
+    push_erased!(shown, 1, std::fmt::Display);
+    push_erased!(shown, "two", std::fmt::Display);
+    assert_eq!(shown.iter().map(|s| s.to_string()).collect::<Vec<_>>(), ["1", "two"]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn refcell_erase_imported_by_path() {
+    let a = refcell_erase!(String::new(), std::fmt::Write);
+    a.borrow_mut().write_str("x").unwrap();
+    let b = refcell_erase!(Rc, 5u8, std::fmt::Debug);
+    assert_eq!(format!("{:?}", b.borrow()), "5");
+}
+
+#[test]
+fn sealed_erase_imported_by_path() {
+    let a = sealed_erase!(1u8, std::fmt::Debug, [u8, u16]);
+    assert_eq!(format!("{:?}", a), "1");
+}
+
+trait Approved {}
+
+impl Approved for u8 {}
+
+sealed_eraser!(erase_approved, std::fmt::Debug, Approved);
+
+#[test]
+fn sealed_eraser_imported_by_path() {
+    assert_eq!(format!("{:?}", erase_approved(4u8)), "4");
+}
+
+#[test]
+fn let_erased_imported_by_path() {
+    let_erased!(mut evens: Iterator<Item = u32> = (1..5).filter(|x| x % 2 == 0));
+    assert_eq!(evens.next(), Some(2));
+}
+
+#[test]
+fn erase_each_imported_by_path() {
+    let shown: Vec<String> = erase_each!([1, 2].iter(), std::fmt::Debug)
+        .map(|item| format!("{:?}", item))
+        .collect();
+    assert_eq!(shown, ["1", "2"]);
+}
+
+#[test]
+fn erase_with_guard_imported_by_path() {
+    let released = std::cell::Cell::new(false);
+    drop(erase_with_guard!(1, std::fmt::Debug, || released.set(true)));
+    assert!(released.get());
+}
+
+#[test]
+fn erase_add_imported_by_path() {
+    assert_eq!(erase_add!(1, 2, std::ops::Add<Output = i32>), 3);
+}
+
+#[test]
+fn loop_eraser_imported_by_path() {
+    let e = loop_eraser!(std::ops::Add<Output = i32>);
+    let mut total = 0;
+    for i in 1..4 {
+        total += e(i) + e(0);
+    }
+    assert_eq!(total, 6);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn match_box_erase_imported_by_path() {
+    let shown = match_box_erase!(Some(3), std::fmt::Debug, {
+        Some(x) => x,
+        None => "none",
+    });
+    assert_eq!(format!("{:?}", shown), "3");
+}
+
+#[cfg(feature = "std")]
+once_erase!(greeting, std::fmt::Display + Send + Sync, String::from("hi"));
+
+#[cfg(feature = "std")]
+#[test]
+fn once_erase_imported_by_path() {
+    assert_eq!(greeting().to_string(), "hi");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn erase_partition_imported_by_path() {
+    let (evens, odds) = erase_partition!(1..6, std::fmt::Debug, |x| x % 2 == 0);
+    assert_eq!(format!("{:?} {:?}", evens, odds), "[2, 4] [1, 3, 5]");
+}
+
+#[cfg(feature = "nightly-tait")]
+mod tait {
+    use whiteout::erased_type;
+
+    erased_type!(pub type Erased = impl std::fmt::Debug; pub fn erase(i32));
+
+    #[test]
+    fn erased_type_imported_by_path() {
+        let value: Erased = erase(3);
+        assert_eq!(format!("{:?}", value), "3");
+    }
+}
+
+#[test]
+fn erase_imported_by_path() {
+    let a = erase!(1u8, std::fmt::Debug);
+    let (b, c) = tuple_erase!((2, 3), std::ops::Add<Output = i32>);
+    assert_eq!(format!("{:?} {}", a, b + c), "1 5");
+}
+
+erase_named!(struct Named, std::fmt::Debug);
+
+#[test]
+fn erase_named_imported_by_path() {
+    let named = erase_named!(Named, 6, std::fmt::Debug);
+    assert_eq!(format!("{:?}", *named), "6");
+}
+
+#[test]
+fn erase_toggle_imported_by_path() {
+    let a = erase_toggle!(const true, 7, std::fmt::Debug);
+    let b = erase_toggle!(const 1 > 0, 8, std::fmt::Debug);
+    assert_eq!(format!("{:?}", a), "7");
+    assert!(b.is_erased());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn erase_map_keys_imported_by_path() {
+    let mut map = std::collections::HashMap::new();
+    map.insert(1, "one");
+    let (map, erase_key) = erase_map_keys!(map, std::hash::Hash + Eq);
+    assert_eq!(map.get(&erase_key(1)), Some(&"one"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trait_object_macros_imported_by_path() {
+    use std::fmt::Debug;
+
+    let boxed = boxerase!(1, Debug);
+    let arc = arc_erase!(2, Debug);
+    let rc = rc_erase!(3, Debug);
+    let cloned = clone_erase!(4, Debug).clone();
+    let cow = cow_erase!(5, Debug);
+    let slice = erase_into_boxed_slice!([6, 7], Debug);
+    let pinned = pin_erase!(8, Debug);
+    let small: whiteout::small::SmallErased<dyn Debug, 1> = small_erase!(9, Debug);
+    let upcast = upcast_erase!(boxerase!(10, Debug + Send), Debug);
+    let shown = format!("{:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+        boxed, arc, rc, &*cloned, &*cow, slice, pinned, &*small, upcast);
+    assert_eq!(shown, "1 2 3 4 5 [6, 7] 8 9 10");
+}
+
+eraser_with_default!(erase_count, zero_count: u32, std::ops::Add<Output = u32>);
+
+#[test]
+fn eraser_with_default_imported_by_path() {
+    assert_eq!(erase_count(2) + zero_count!(), 2);
+}
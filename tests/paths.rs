@@ -0,0 +1,50 @@
+//! Checks that traits named by paths starting with `::` keep their meaning in
+//! the macros which make trait objects.
+
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+extern crate whiteout;
+
+pub trait Shown {
+    fn shown(&self) -> String;
+}
+
+impl Shown for i32 {
+    fn shown(&self) -> String {
+        format!("root {}", self)
+    }
+}
+
+mod inner {
+    // A trait of the same name, which `::Shown` must not pick
+    pub trait Shown {
+        fn shown(&self) -> String;
+    }
+
+    impl Shown for i32 {
+        fn shown(&self) -> String {
+            format!("inner {}", self)
+        }
+    }
+
+    pub fn boxed() -> Box<dyn (::Shown) + Send> {
+        boxerase!(1, ::Shown + Send)
+    }
+
+    pub fn counted() -> std::rc::Rc<dyn (::std::fmt::Debug)> {
+        rc_erase!(2, ::std::fmt::Debug)
+    }
+
+    pub fn small() -> whiteout::small::SmallErased<dyn (::Shown), 1> {
+        small_erase!(3, ::Shown)
+    }
+}
+
+#[test]
+fn root_paths_in_submodule() {
+    assert_eq!(inner::boxed().shown(), "root 1");
+    assert_eq!(format!("{:?}", inner::counted()), "2");
+    assert_eq!(inner::small().shown(), "root 3");
+    assert_eq!(inner::Shown::shown(&4), "inner 4");
+}
@@ -0,0 +1,65 @@
+//! Checks that generic wrappers which only carry their parameter in
+//! `PhantomData` erase like any other type. The parameter doesn't appear in
+//! the erased type, but, as with any generic type, `Wrapper<u32>` and
+//! `Wrapper<String>` still erase to different anonymous types; boxing them
+//! stores them together.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::marker::PhantomData;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+struct Wrapper<T> {
+    id: u8,
+    marker: PhantomData<T>,
+}
+
+impl<T> Wrapper<T> {
+    fn new(id: u8) -> Self {
+        Wrapper { id, marker: PhantomData }
+    }
+}
+
+impl<T> Describe for Wrapper<T> {
+    fn describe(&self) -> String {
+        format!("wrapper {}", self.id)
+    }
+}
+
+eraser!(erase_describe, Describe);
+
+fn describe_both<A: Describe, B: Describe>(a: &A, b: &B) -> String {
+    format!("{}, {}", a.describe(), b.describe())
+}
+
+#[test]
+fn shared_eraser() {
+    let a = erase_describe(Wrapper::<u32>::new(1));
+    let b = erase_describe(Wrapper::<String>::new(2));
+    assert_eq!(describe_both(&a, &b), "wrapper 1, wrapper 2");
+}
+
+#[test]
+fn same_parameter_same_type() {
+    let values = [
+        erase_describe(Wrapper::<u32>::new(1)),
+        erase_describe(Wrapper::<u32>::new(2)),
+    ];
+    let described: Vec<String> = values.iter().map(Describe::describe).collect();
+    assert_eq!(described, ["wrapper 1", "wrapper 2"]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn boxed_together() {
+    let values: Vec<Box<dyn Describe>> = vec![
+        boxerase!(Wrapper::<u32>::new(1), Describe),
+        boxerase!(Wrapper::<String>::new(2), Describe),
+    ];
+    let described: Vec<String> = values.iter().map(|v| v.describe()).collect();
+    assert_eq!(described, ["wrapper 1", "wrapper 2"]);
+}
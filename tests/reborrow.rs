@@ -0,0 +1,23 @@
+//! Checks that `erase_mut!` reborrows its argument, so the same `&mut` can be
+//! erased more than once.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::io::Write;
+
+#[test]
+fn erase_same_mut_twice() {
+    let mut buf: Vec<u8> = Vec::new();
+    let r: &mut Vec<u8> = &mut buf;
+    {
+        let first = erase_mut!(r, Write);
+        first.write_all(b"first ").unwrap();
+    }
+    {
+        let second = erase_mut!(r, Write);
+        second.write_all(b"second").unwrap();
+    }
+    r.push(b'!');
+    assert_eq!(buf, b"first second!");
+}
@@ -0,0 +1,38 @@
+//! Checks that `sealed_eraser!` accepts the types with the marker and hides
+//! the marker from the erased type.
+
+#[macro_use]
+extern crate whiteout;
+
+mod internal {
+    pub trait Marker {}
+
+    impl Marker for u8 {}
+    impl Marker for &'static str {}
+}
+
+trait Pair<A, B> {
+    fn pair(&self) -> (A, B);
+}
+
+impl Pair<u8, char> for u8 {
+    fn pair(&self) -> (u8, char) {
+        (*self, char::from(b'a' + *self))
+    }
+}
+
+sealed_eraser!(erase_sealed, std::fmt::Debug, internal::Marker);
+
+#[test]
+fn marked_types_are_erased() {
+    let a = erase_sealed(7u8);
+    let b = erase_sealed("seven");
+    assert_eq!(format!("{:?} {:?}", a, b), "7 \"seven\"");
+}
+
+#[test]
+fn generic_trait_before_marker() {
+    sealed_eraser!(erase_pair, Pair<u8, char>, internal::Marker);
+    let a = erase_pair(2u8);
+    assert_eq!(a.pair(), (2, 'c'));
+}
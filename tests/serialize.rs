@@ -0,0 +1,28 @@
+//! Checks that values of different types erased by `erase_serialize!` can be
+//! serialized together.
+
+#![cfg(feature = "serde")]
+
+extern crate erased_serde;
+extern crate serde_json;
+#[macro_use]
+extern crate whiteout;
+
+#[test]
+fn serialize_mixed_types() {
+    let values = vec![
+        erase_serialize!(1u8),
+        erase_serialize!("two"),
+        erase_serialize!(vec![3.5, 4.0]),
+    ];
+    assert_eq!(serde_json::to_string(&values).unwrap(), r#"[1,"two",[3.5,4.0]]"#);
+}
+
+#[test]
+fn serialize_with_bounds() {
+    let values: Vec<Box<dyn erased_serde::Serialize + Send>> = vec![
+        erase_serialize!(Some(5i32), Send),
+        erase_serialize!((), Send),
+    ];
+    assert_eq!(serde_json::to_string(&values).unwrap(), "[5,null]");
+}
@@ -0,0 +1,117 @@
+//! Checks that `SmallErased` stores values inline or boxed as appropriate,
+//! and drops them exactly once either way.
+
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+extern crate whiteout;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use whiteout::small::SmallErased;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+struct Tracked<P> {
+    drops: Rc<Cell<u32>>,
+    payload: P,
+}
+
+impl<P: std::fmt::Debug> Describe for Tracked<P> {
+    fn describe(&self) -> String {
+        format!("{:?}", self.payload)
+    }
+}
+
+impl<P> Drop for Tracked<P> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[test]
+fn inline_value() {
+    let drops = Rc::new(Cell::new(0));
+    let a: SmallErased<dyn Describe, 2> = small_erase!(Tracked { drops: drops.clone(), payload: 1u8 }, Describe);
+    assert!(a.is_inline());
+    assert_eq!(a.describe(), "1");
+    assert_eq!(drops.get(), 0);
+    drop(a);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn boxed_value() {
+    let drops = Rc::new(Cell::new(0));
+    let a: SmallErased<dyn Describe, 2> = small_erase!(Tracked { drops: drops.clone(), payload: [7u64; 4] }, Describe);
+    assert!(!a.is_inline());
+    assert_eq!(a.describe(), "[7, 7, 7, 7]");
+    assert_eq!(drops.get(), 0);
+    drop(a);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn moved_value() {
+    let drops = Rc::new(Cell::new(0));
+    let values: Vec<SmallErased<dyn Describe, 2>> = (0..3u8)
+        .map(|i| small_erase!(Tracked { drops: drops.clone(), payload: i }, Describe))
+        .collect();
+    let moved: Vec<SmallErased<dyn Describe, 2>> = values.into_iter().rev().collect();
+    let described: Vec<String> = moved.iter().map(|v| v.describe()).collect();
+    assert_eq!(described, ["2", "1", "0"]);
+    assert_eq!(drops.get(), 0);
+    drop(moved);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn overaligned_value_is_boxed() {
+    #[repr(align(64))]
+    struct Aligned(u8);
+
+    impl Describe for Aligned {
+        fn describe(&self) -> String {
+            format!("{}", self.0)
+        }
+    }
+
+    let a: SmallErased<dyn Describe, 8> = small_erase!(Aligned(3), Describe);
+    assert!(!a.is_inline());
+    assert_eq!(a.describe(), "3");
+}
+
+trait Bump {
+    fn bump(&self) -> u64;
+}
+
+impl Bump for Cell<u32> {
+    fn bump(&self) -> u64 {
+        self.set(self.get() + 1);
+        u64::from(self.get())
+    }
+}
+
+impl Bump for Cell<[u64; 2]> {
+    fn bump(&self) -> u64 {
+        let [a, b] = self.get();
+        self.set([a + 1, b]);
+        a + 1 + b
+    }
+}
+
+// Run under Miri to check that the storage allows writes through `&self`
+#[test]
+fn interior_mutability_through_trait_object() {
+    let inline: SmallErased<dyn Bump, 1> = small_erase!(Cell::new(0u32), Bump);
+    assert!(inline.is_inline());
+    assert_eq!(inline.bump(), 1);
+    assert_eq!(inline.bump(), 2);
+
+    let boxed: SmallErased<dyn Bump, 1> = small_erase!(Cell::new([0, 10]), Bump);
+    assert!(!boxed.is_inline());
+    assert_eq!(boxed.bump(), 11);
+    assert_eq!(boxed.bump(), 12);
+}
@@ -0,0 +1,36 @@
+//! Checks that `spawn_erased!` runs the closure on another thread and gives
+//! back its result erased.
+
+#![cfg(feature = "std")]
+
+#[macro_use]
+extern crate whiteout;
+
+use std::thread;
+
+trait Job {
+    fn run(&mut self) -> u64;
+}
+
+struct Squares {
+    upto: u64,
+}
+
+impl Job for Squares {
+    fn run(&mut self) -> u64 {
+        (1..=self.upto).map(|i| i * i).sum()
+    }
+}
+
+#[test]
+fn join_erased_result() {
+    let spawner = thread::current().id();
+    let handle = spawn_erased!(Squares { upto: 3 }, Job + Send, move |mut job| {
+        assert_ne!(thread::current().id(), spawner);
+        assert_eq!(job.run(), 14);
+        job.upto = 4;
+        job
+    });
+    let mut job = handle.join().unwrap();
+    assert_eq!(job.run(), 30);
+}
@@ -0,0 +1,56 @@
+//! Checks that every supertrait of a compound trait is usable on values
+//! erased to it.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Sub};
+
+trait Arithmetic:
+    Add<Self, Output = Self>
+    + Sub<Self, Output = Self>
+    + Mul<Self, Output = Self>
+    + From<i32>
+    + Debug
+    + PartialEq
+    + Sized
+{}
+
+impl<T> Arithmetic for T
+    where T: Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + From<i32> + Debug + PartialEq
+{}
+
+eraser!(erase_arithmetic, Arithmetic);
+
+#[test]
+fn add() {
+    assert_eq!(erase_arithmetic(2) + erase_arithmetic(3), 5.into());
+}
+
+#[test]
+fn sub() {
+    assert_eq!(erase_arithmetic(2) - erase_arithmetic(3), (-1).into());
+}
+
+#[test]
+fn mul() {
+    assert_eq!(erase_arithmetic(2) * erase_arithmetic(3), 6.into());
+}
+
+#[test]
+fn debug() {
+    assert_eq!(format!("{:?}", erase_arithmetic(2.5)), "2.5");
+}
+
+#[test]
+fn eq() {
+    assert!(erase_arithmetic(2) == erase_arithmetic(2));
+    assert!(erase_arithmetic(2) != erase_arithmetic(3));
+}
+
+#[test]
+fn combined() {
+    let [a, b, c] = [1, 2, 3].map(erase_arithmetic);
+    assert_eq!(a * b + c - 1.into(), 4.into());
+}
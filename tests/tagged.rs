@@ -0,0 +1,25 @@
+//! Checks that the type ids from `erase_tagged!` group erased values by the
+//! type they came from.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::any::TypeId;
+use std::collections::HashMap;
+
+#[test]
+fn group_by_type_id() {
+    let (a, a_id) = erase_tagged!(1i32, std::fmt::Debug);
+    let (b, b_id) = erase_tagged!(2i32, std::fmt::Debug);
+    let (c, c_id) = erase_tagged!(3u8, std::fmt::Debug);
+
+    let mut buckets: HashMap<TypeId, Vec<String>> = HashMap::new();
+    let tagged = [(a_id, format!("{:?}", a)), (b_id, format!("{:?}", b)), (c_id, format!("{:?}", c))];
+    for (id, shown) in tagged.iter() {
+        buckets.entry(*id).or_default().push(shown.clone());
+    }
+
+    assert_eq!(buckets.len(), 2);
+    assert_eq!(buckets[&TypeId::of::<i32>()], ["1", "2"]);
+    assert_eq!(buckets[&TypeId::of::<u8>()], ["3"]);
+}
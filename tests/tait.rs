@@ -0,0 +1,36 @@
+//! Checks that `erased_type!` defines a nameable erased type; needs a nightly
+//! compiler, so run with `cargo +nightly test --features nightly-tait`.
+
+#![cfg_attr(feature = "nightly-tait", feature(type_alias_impl_trait))]
+
+#[cfg(feature = "nightly-tait")]
+#[macro_use]
+extern crate whiteout;
+
+#[cfg(feature = "nightly-tait")]
+mod tait {
+    pub trait MyTrait: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+    impl<T> MyTrait for T
+        where T: std::ops::Add<Self, Output=Self> + From<i32> + std::fmt::Debug + PartialEq {}
+
+    erased_type!(pub type Erased = impl MyTrait; pub fn erase(i64));
+
+    struct Totals {
+        first: Erased,
+        second: Erased,
+    }
+
+    #[test]
+    fn erased_type_as_field() {
+        let totals = Totals { first: erase(1), second: erase(2) };
+        assert_eq!(totals.first + totals.second, 3.into());
+    }
+
+    #[test]
+    fn erased_type_in_signature() {
+        fn add_one(val: Erased) -> Erased {
+            val + 1.into()
+        }
+        assert_eq!(format!("{:?}", add_one(erase(4))), "5");
+    }
+}
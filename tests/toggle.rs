@@ -0,0 +1,28 @@
+//! Checks `erase_toggle!` with a const generic flag in both settings.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::fmt::Debug;
+use whiteout::erased::Toggled;
+
+fn wrap<const ERASE: bool, T: Debug>(val: T) -> Toggled<impl Debug, T> {
+    erase_toggle!(const ERASE, val, Debug)
+}
+
+#[test]
+fn erased_when_true() {
+    let toggled = wrap::<true, _>(vec![1, 2]);
+    assert!(toggled.is_erased());
+    match toggled {
+        Toggled::Erased(val) => assert_eq!(format!("{:?}", val), "[1, 2]"),
+        Toggled::Concrete(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn concrete_when_false() {
+    let toggled = wrap::<false, _>(vec![1, 2]);
+    assert!(!toggled.is_erased());
+    assert_eq!(toggled.concrete().map(|v| v.len()), Some(2));
+}
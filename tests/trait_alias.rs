@@ -0,0 +1,13 @@
+//! Checks that trait aliases can be used as the trait; needs a nightly
+//! compiler, so run with `cargo +nightly test --features nightly-trait-alias`.
+
+#![cfg_attr(feature = "nightly-trait-alias", feature(trait_alias))]
+
+// In a separate file, since the alias syntax is rejected when it's parsed
+#[cfg(feature = "nightly-trait-alias")]
+#[macro_use]
+extern crate whiteout;
+
+#[cfg(feature = "nightly-trait-alias")]
+#[path = "trait_alias/alias.rs"]
+mod alias;
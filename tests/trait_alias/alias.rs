@@ -0,0 +1,18 @@
+trait Number = std::ops::Add<i32, Output = i32> + std::fmt::Debug + Copy;
+
+eraser!(erase_number, Number);
+
+#[test]
+fn erase_with_alias() {
+    let a = erase!(1, Number);
+    assert_eq!(format!("{:?}", a), "1");
+    assert_eq!(a + 2, 3);
+}
+
+#[test]
+fn eraser_with_alias() {
+    let a = erase_number(5);
+    let b = a;
+    assert_eq!(format!("{:?}", b), "5");
+    assert_eq!(a + 1, 6);
+}
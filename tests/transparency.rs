@@ -0,0 +1,62 @@
+//! Property tests that erasure doesn't change behavior: random operations on
+//! erased values give the same results as on the concrete values.
+
+#[macro_use]
+extern crate proptest;
+#[macro_use]
+extern crate whiteout;
+
+use proptest::collection::vec;
+use std::ops::{Add, Mul, Sub};
+
+trait Arith: Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Copy + Into<i64> {}
+
+impl<T> Arith for T where T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy + Into<i64> {}
+
+trait Compare: PartialOrd + Ord + Copy + Into<i64> {}
+
+impl<T> Compare for T where T: PartialOrd + Ord + Copy + Into<i64> {}
+
+eraser!(erase_arith, Arith);
+eraser!(erase_compare, Compare);
+
+proptest! {
+    // The operands are small enough that a few additions, subtractions and
+    // multiplications by less than 4 can't overflow.
+    #[test]
+    fn arithmetic_is_transparent(
+        start in -1000i32..=1000,
+        ops in vec((0u8..3, -1000i32..=1000), 0..5)
+    ) {
+        let mut concrete = start;
+        let mut erased = erase_arith(start);
+        for (op, n) in ops {
+            match op {
+                0 => {
+                    concrete += n;
+                    erased = erased + erase_arith(n);
+                }
+                1 => {
+                    concrete -= n;
+                    erased = erased - erase_arith(n);
+                }
+                _ => {
+                    concrete *= n % 4;
+                    erased = erased * erase_arith(n % 4);
+                }
+            }
+            let value: i64 = erased.into();
+            prop_assert_eq!(value, i64::from(concrete));
+        }
+    }
+
+    #[test]
+    fn comparison_is_transparent(a in -1000i32..=1000, b in -8i32..8) {
+        let (x, y) = (erase_compare(a), erase_compare(b));
+        prop_assert_eq!(x < y, a < b);
+        prop_assert_eq!(x == y, a == b);
+        prop_assert_eq!(x.cmp(&y), a.cmp(&b));
+        let max: i64 = x.max(y).into();
+        prop_assert_eq!(max, i64::from(a.max(b)));
+    }
+}
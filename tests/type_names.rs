@@ -0,0 +1,23 @@
+//! Checks the type names recorded by `erase_with_type_name!`; run with
+//! `--features debug-typenames`.
+
+#![cfg(feature = "debug-typenames")]
+
+#[macro_use]
+extern crate whiteout;
+
+#[test]
+fn records_concrete_type() {
+    let a = erase_with_type_name!(10u8, std::fmt::Debug);
+    let b = erase_with_type_name!("hi", std::fmt::Display);
+    assert_eq!(a.type_name(), "u8");
+    assert_eq!(b.type_name(), "&str");
+}
+
+#[test]
+fn erased_value_is_usable() {
+    let a = erase_with_type_name!(vec![1, 2], std::fmt::Debug + Clone);
+    assert_eq!(format!("{:?}", a), "[1, 2]");
+    assert_eq!(format!("{:?}", a.clone()), "[1, 2]");
+    assert_eq!(format!("{:?}", a.into_inner()), "[1, 2]");
+}
@@ -0,0 +1,10 @@
+//! Checks the errors given for misused erasures against the snapshots in
+//! `tests/ui`.
+
+extern crate trybuild;
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate whiteout;
+
+const TEN: () = {
+    let _ = erase!(10, Copy);
+};
+
+fn main() {
+    let _ = TEN;
+}
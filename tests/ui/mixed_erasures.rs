@@ -0,0 +1,8 @@
+#[macro_use]
+extern crate whiteout;
+
+fn main() {
+    let a = erase!(10, std::ops::Add<Output = i32>);
+    let b = erase!(5, std::ops::Add<Output = i32>);
+    let _ = a + b;
+}
@@ -0,0 +1,8 @@
+#[macro_use]
+extern crate whiteout;
+
+struct Opaque;
+
+fn main() {
+    let _ = erase!(Opaque, std::fmt::Debug);
+}
@@ -0,0 +1,29 @@
+//! Checks that `eraser_with_default!`'s default has the eraser's own type and
+//! that `Default` lands in the trait rather than in the `where` clause.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::ops::Add;
+
+eraser_with_default!(erase_sum, empty_sum: u64, Add<Output = u64>);
+
+eraser_with_default!(erase_text, empty_text: String,
+    std::fmt::Write + AsRef<str> where String: Clone);
+
+#[test]
+fn default_combines_with_erased() {
+    let total = erase_sum(4) + empty_sum!();
+    assert_eq!(total, 4);
+}
+
+#[test]
+fn default_before_where() {
+    use std::fmt::Write;
+    let mut text = empty_text!();
+    text.write_str("ab").unwrap();
+    let fresh = whiteout::default_like(&erase_text("c".to_string()));
+    let both = [text, fresh];
+    assert_eq!(both[0].as_ref(), "ab");
+    assert_eq!(both[1].as_ref(), "");
+}
@@ -0,0 +1,76 @@
+//! Checks that erasure is the identity: an erased value has the same size
+//! and alignment as the original, and behaves the same.
+
+#[macro_use]
+extern crate whiteout;
+
+use std::fmt::Debug;
+use std::mem::{align_of_val, size_of_val};
+use std::ops::Add;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+    x: u8,
+    y: u32,
+}
+
+#[derive(Debug)]
+struct Empty;
+
+/// Asserts that erasing `$val` into `$($tr)*` leaves its layout unchanged.
+macro_rules! assert_same_layout {
+    ($val:expr, $($tr:tt)*) => {
+        {
+            let original = $val;
+            let erased = erase!($val, $($tr)*);
+            assert_eq!(size_of_val(&erased), size_of_val(&original));
+            assert_eq!(align_of_val(&erased), align_of_val(&original));
+        }
+    };
+}
+
+#[test]
+fn primitives_keep_layout() {
+    assert_same_layout!(1u8, Debug);
+    assert_same_layout!(1u16, Debug);
+    assert_same_layout!(1u64, Debug);
+    assert_same_layout!(1.0f32, Debug);
+    assert_same_layout!('a', Debug);
+    assert_same_layout!(true, Debug);
+}
+
+#[test]
+fn structs_keep_layout() {
+    assert_same_layout!(Point { x: 1, y: 2 }, Debug + Clone);
+    assert_same_layout!(Empty, Debug);
+    assert_same_layout!([0u16; 7], Debug);
+    assert_same_layout!((1u8, 2u64), Debug);
+}
+
+#[test]
+fn references_point_at_the_original() {
+    let data = [1u32, 2, 3];
+    let erased = erase_ref!(&data, Debug);
+    assert_eq!(size_of_val(erased), size_of_val(&data));
+    assert_eq!(erased as *const _ as *const u32, data.as_ptr());
+}
+
+#[test]
+fn erased_structs_compare_like_originals() {
+    let p = Point { x: 1, y: 2 };
+    let erased = erase!(p, PartialEq<Point>);
+    assert!(erased == p);
+    assert!(erased != Point { x: 2, y: 1 });
+}
+
+#[test]
+fn erased_add_behaves_like_concrete_add() {
+    eraser!(erase_add, Add<i64, Output = i64>);
+    let mut concrete = 0i64;
+    let mut erased = 0i64;
+    for i in 0..1000 {
+        concrete += i;
+        erased = erase_add(erased) + i;
+    }
+    assert_eq!(concrete, erased);
+}
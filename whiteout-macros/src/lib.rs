@@ -0,0 +1,162 @@
+//! Procedural macros for `whiteout`. These are re-exported from the
+//! `whiteout` crate and should be used from there.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+/// Erases the return type of a function into `impl Trait` for the trait
+/// given as the argument of the attribute.
+///
+/// The declared return type is still used to type-check the body, so every
+/// `return` and the tail expression produce that type, which is then erased.
+///
+/// The body is moved into a closure for this, so `async fn` and `const fn`
+/// aren't supported: a closure can't `.await`, and can't be called in a
+/// const context. Either gives a compile error.
+#[proc_macro_attribute]
+pub fn erase(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let tr: Vec<TokenTree> = attr.into_iter().collect();
+    if tr.is_empty() {
+        return error("#[erase] needs a trait, as in #[erase(MyTrait)]");
+    }
+    let tokens: Vec<TokenTree> = item.into_iter().collect();
+    match split_fn(&tokens) {
+        Some(ref parts) if parts.qualifier("async") => {
+            error("#[erase] can't be applied to an async fn; erase the returned future instead")
+        }
+        Some(ref parts) if parts.qualifier("const") => {
+            error("#[erase] can't be applied to a const fn")
+        }
+        Some(parts) => {
+            let mut out: Vec<TokenTree> = parts.sig.to_vec();
+            out.extend(arrow());
+            out.push(ident("impl"));
+            out.extend(tr.iter().cloned());
+            out.extend(parts.wh.iter().cloned());
+            out.push(wrap_body(parts.ret, parts.body));
+            TokenStream::from_iter(out)
+        }
+        None => error("#[erase] can only be applied to a function with a return type"),
+    }
+}
+
+/// The pieces of a function item that `#[erase]` rearranges.
+struct FnParts<'a> {
+    /// Everything up to and including the argument list
+    sig: &'a [TokenTree],
+    /// The declared return type
+    ret: &'a [TokenTree],
+    /// The where clause, if any
+    wh: &'a [TokenTree],
+    body: &'a Group,
+}
+
+impl<'a> FnParts<'a> {
+    /// Whether the qualifiers before `fn` include the given keyword.
+    fn qualifier(&self, name: &str) -> bool {
+        self.sig.iter().take_while(|t| !is_ident(t, "fn")).any(|t| is_ident(t, name))
+    }
+}
+
+/// Splits a function into the signature before `->`, the return type, the
+/// where clause (if any) and the body.
+fn split_fn<'a>(tokens: &'a [TokenTree]) -> Option<FnParts<'a>> {
+    let (body, rest) = match tokens.split_last() {
+        Some((TokenTree::Group(g), rest)) if g.delimiter() == Delimiter::Brace => (g, rest),
+        _ => return None,
+    };
+
+    // Skip past the generics so that any `->` inside them is not mistaken
+    // for the return arrow; the argument list is the first parenthesized
+    // group after that.
+    let name = rest.iter().position(|t| is_ident(t, "fn"))? + 1;
+    let mut i = name + 1;
+    let mut depth = 0;
+    while i < rest.len() {
+        match rest[i] {
+            TokenTree::Punct(ref p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(ref p) if p.as_char() == '>' && !is_arrow_end(rest, i) => depth -= 1,
+            TokenTree::Group(ref g) if depth == 0 && g.delimiter() == Delimiter::Parenthesis => break,
+            _ => {}
+        }
+        i += 1;
+    }
+    let args = i;
+
+    if args + 2 >= rest.len() || !is_punct(&rest[args + 1], '-') || !is_punct(&rest[args + 2], '>') {
+        return None;
+    }
+    let ret_start = args + 3;
+    let wh = rest[ret_start..]
+        .iter()
+        .position(|t| is_ident(t, "where"))
+        .map_or(rest.len(), |p| ret_start + p);
+    if wh == ret_start {
+        return None;
+    }
+
+    Some(FnParts {
+        sig: &rest[..=args],
+        ret: &rest[ret_start..wh],
+        wh: &rest[wh..],
+        body,
+    })
+}
+
+/// Wraps the original body in a closure returning the declared type so that
+/// early returns and `?` keep working, then returns its result.
+fn wrap_body(ret: &[TokenTree], body: &Group) -> TokenTree {
+    let mut closure = vec![
+        ident("move"),
+        TokenTree::Punct(Punct::new('|', Spacing::Joint)),
+        TokenTree::Punct(Punct::new('|', Spacing::Alone)),
+    ];
+    closure.extend(arrow());
+    closure.extend(ret.iter().cloned());
+    closure.push(TokenTree::Group(body.clone()));
+
+    let call = vec![
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::from_iter(closure))),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::new())),
+    ];
+    TokenTree::Group(Group::new(Delimiter::Brace, TokenStream::from_iter(call)))
+}
+
+fn arrow() -> Vec<TokenTree> {
+    vec![
+        TokenTree::Punct(Punct::new('-', Spacing::Joint)),
+        TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+    ]
+}
+
+fn ident(name: &str) -> TokenTree {
+    TokenTree::Ident(Ident::new(name, Span::call_site()))
+}
+
+fn is_ident(t: &TokenTree, name: &str) -> bool {
+    match *t {
+        TokenTree::Ident(ref i) => i.to_string() == name,
+        _ => false,
+    }
+}
+
+fn is_punct(t: &TokenTree, c: char) -> bool {
+    match *t {
+        TokenTree::Punct(ref p) => p.as_char() == c,
+        _ => false,
+    }
+}
+
+/// Whether the `>` at index `i` is the second half of a `->`.
+fn is_arrow_end(tokens: &[TokenTree], i: usize) -> bool {
+    i > 0 && match tokens[i - 1] {
+        TokenTree::Punct(ref p) => p.as_char() == '-' && p.spacing() == Spacing::Joint,
+        _ => false,
+    }
+}
+
+fn error(msg: &str) -> TokenStream {
+    format!("compile_error!({:?});", msg).parse().unwrap()
+}